@@ -4,10 +4,11 @@
 
 use std::boxed::Box;
 use std::error::Error;
+use std::sync::Arc;
+use std::thread;
 use tidy::*;
 
 
-//use std::thread;
 //use std::io::{self, Write};
 //use std::time::Duration;
 
@@ -45,7 +46,7 @@ fn test_sub() -> Result<(), Box<dyn Error>> {
       <calories>950</calories>
     </food>
   </breakfast_menu>";
-  let tidy = Tidy::new()?;
+  let mut tidy = Tidy::new()?;
   println!("Tidy release date: {}", tidy.release_date());
   println!("Tidy library version: {}", tidy.library_version());
 
@@ -79,28 +80,38 @@ fn test_sub() -> Result<(), Box<dyn Error>> {
   println!("Tidy warning count: {}", tidy.warning_count());
   println!("Tidy error count: {}", tidy.error_count());
 
-  println!("\nDiagnostics:\n\n {}", TidyUtil::errbuf_as_string(&tidy));
+  println!("\nDiagnostics:\n\n {}", tidy.errbuf().to_string_lossy());
   tidy.save_buffer()?;
   //tidy.save_stdout()?;
   
   //tidy.opt_save_file("./tidyOpts.cfg")?;
-  print!("{}", String::from_utf8_lossy(&TidyUtil::output_as_vector(&tidy).unwrap()));
+  print!("{}", tidy.output().to_string_lossy());
 
   Ok(())
 }
 
 pub fn main() -> Result<(), Box<dyn Error>> {
-  test_sub()
-  /*let handle = thread::spawn(|| {
-    for _i in 1..2 {
-      match  {
-        Err(_e) => panic!(),
-        _ => (),
-      }
-    }
-  });
+  test_sub()?;
+
+  let template = Tidy::new()?;
+  template.opt_set_bool(TidyOptionId::TidyXmlTags, true)?;
+  let pool = Arc::new(TidyPool::new(template));
 
-  handle.join().unwrap();
+  let handles: Vec<_> = (1..3)
+    .map(|i| {
+      let pool = Arc::clone(&pool);
+      thread::spawn(move || -> Result<String, TidyError> {
+        let mut tidy = pool.get()?;
+        tidy.parse_bytes(format!("<thread>{}</thread>", i).as_bytes())?;
+        tidy.save_buffer()?;
+        Ok(tidy.output().to_string_lossy())
+      })
+    })
+    .collect();
 
-  Ok(())*/
+  for handle in handles {
+    print!("{}", handle.join().unwrap()?);
+  }
+
+  Ok(())
 }
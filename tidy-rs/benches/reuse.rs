@@ -0,0 +1,70 @@
+//! Compares creating a new Tidy per document with reusing one instance.
+//!
+//! Run with `cargo bench --bench reuse [-- documents]`.
+
+use std::error::Error;
+use std::time::{Duration, Instant};
+use tidy::*;
+
+const DOCUMENT: &[u8] = b"<!DOCTYPE html><title>Reuse</title>\
+<p>Some <b>bold and <i>nested</b> markup</i>\
+<ul><li>one<li>two<li>three</ul>\
+<table><tr><td>a<td>b</table>";
+
+fn configure(tidy: &Tidy) -> Result<(), TidyError> {
+  tidy.opt_set(opt::IndentContent, opt::TriState::Auto)?;
+  tidy.opt_set(opt::WrapLen, 100)?;
+  tidy.opt_set(opt::Mark, false)?;
+  tidy.opt_set(opt::DropEmptyElems, true)?;
+  tidy.set_char_encoding("utf8")?;
+  Ok(())
+}
+
+fn tidy_document(tidy: &mut Tidy) -> Result<usize, TidyError> {
+  tidy.parse_bytes(DOCUMENT)?;
+  tidy.clean_and_repair()?;
+  tidy.save_buffer()?;
+  Ok(tidy.output().len())
+}
+
+fn new_per_document(documents: usize) -> Result<Duration, TidyError> {
+  let start = Instant::now();
+  for _ in 0..documents {
+    let mut tidy = Tidy::new()?;
+    configure(&tidy)?;
+    tidy_document(&mut tidy)?;
+  }
+  Ok(start.elapsed())
+}
+
+fn reused(documents: usize) -> Result<Duration, TidyError> {
+  let start = Instant::now();
+  let mut tidy = Tidy::new()?;
+  configure(&tidy)?;
+  for _ in 0..documents {
+    tidy_document(&mut tidy)?;
+    tidy.reset()?;
+  }
+  Ok(start.elapsed())
+}
+
+fn report(name: &str, documents: usize, elapsed: Duration) {
+  println!(
+    "{:>16}: {:>8.2?} ({:.0} documents/s)",
+    name,
+    elapsed,
+    documents as f64 / elapsed.as_secs_f64()
+  );
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+  // cargo bench passes --bench to benchmarks without harness.
+  let documents = match std::env::args().skip(1).find(|arg| !arg.starts_with("--")) {
+    Some(arg) => arg.parse()?,
+    None => 10_000,
+  };
+
+  report("new per document", documents, new_per_document(documents)?);
+  report("reused", documents, reused(documents)?);
+  Ok(())
+}
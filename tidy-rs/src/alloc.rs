@@ -0,0 +1,315 @@
+use crate::{panic, TidyUtil};
+use libc::*;
+use std::alloc::{GlobalAlloc, Layout};
+use std::cell::Cell;
+use tidy_sys::*;
+
+// Every block is preceded by a header holding its size, as tidy's free and realloc calls don't pass it, and linking it
+// into the list of the allocator's blocks, which are freed without tidy when the document was poisoned. The blocks
+// are aligned like malloc's.
+const ALIGN: usize = 16;
+const HEADER: usize = 32;
+
+#[repr(C)]
+struct Header {
+  size: usize,
+  prev: *mut Header,
+  next: *mut Header,
+}
+
+/// Allocator configuration for a Tidy instance, see [`Tidy::with_allocator`](crate::Tidy::with_allocator).
+///
+/// All memory libtidy allocates for the document, i.e. the parsed tree, lexer and config, as well as the error and
+/// output buffers is requested from the configured allocator and accounted per instance. Buffers taken from the
+/// instance, e.g. by take_output(), are copied to tidy's default allocator, as they can outlive it.
+///
+/// With a limit set, an allocation that would take the document's memory use beyond it abandons the current operation,
+/// which returns TidyError::MemoryLimit. The limit applies to creating the instance, parsing, repairing, diagnosing and
+/// saving, memory allocated by other calls, e.g. for option values, is accounted but not limited. As after a panic,
+/// the instance can't be used afterwards and all memory of its document is freed when it's dropped.
+///
+/// ```
+/// # use tidy::*;
+/// # fn main() -> Result<(), TidyError> {
+/// let mut tidy = Tidy::with_allocator(Allocator::global().limit(64 * 1024))?;
+/// let mut input = b"<title>T</title>".to_vec();
+/// for _ in 0..10_000 {
+///   input.extend_from_slice(b"<p class=\"c\">paragraph</p>");
+/// }
+/// match tidy.parse_bytes(&input) {
+///   Err(TidyError::MemoryLimit { limit, .. }) => assert_eq!(limit, 64 * 1024),
+///   other => panic!("unexpected {:?}", other),
+/// }
+/// let usage = tidy.memory_usage().unwrap();
+/// assert!(usage.exceeded && usage.peak <= 64 * 1024);
+/// assert!(matches!(tidy.clean_and_repair(), Err(TidyError::MemoryLimit { .. })));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// If the allocator fails, the current operation is abandoned and returns TidyError::Panic. The instance can't be used
+/// afterwards.
+///
+/// ```
+/// # use tidy::*;
+/// # use std::alloc::{GlobalAlloc, Layout, System};
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// // Fails all allocations beyond a budget.
+/// struct Budget(AtomicUsize);
+///
+/// unsafe impl GlobalAlloc for Budget {
+///   unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+///     if self.0.fetch_add(layout.size(), Ordering::Relaxed) > 1 << 20 {
+///       std::ptr::null_mut()
+///     } else {
+///       System.alloc(layout)
+///     }
+///   }
+///
+///   unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+///     System.dealloc(ptr, layout)
+///   }
+/// }
+///
+/// # fn main() -> Result<(), TidyError> {
+/// let mut tidy = Tidy::with_allocator(Allocator::new(Budget(AtomicUsize::new(0))))?;
+/// let input = "<p>paragraph".repeat(100_000);
+/// match tidy.parse_bytes(input.as_bytes()) {
+///   Err(TidyError::Panic { message, .. }) => assert!(message.starts_with("Out of memory")),
+///   other => panic!("unexpected {:?}", other),
+/// }
+/// assert!(matches!(tidy.clean_and_repair(), Err(TidyError::Panic { .. })));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Allocator {
+  alloc: Box<dyn GlobalAlloc + Send + Sync>,
+  limit: Option<usize>,
+}
+
+impl Allocator {
+  /// Route allocations through Rust's global allocator.
+  pub fn global() -> Allocator {
+    Allocator::new(RustGlobal)
+  }
+
+  /// Route allocations through given allocator.
+  pub fn new<A: GlobalAlloc + Send + Sync + 'static>(alloc: A) -> Allocator {
+    Allocator {
+      alloc: Box::new(alloc),
+      limit: None,
+    }
+  }
+
+  /// Set the number of bytes the document may use before the parse is stopped.
+  pub fn limit(mut self, bytes: usize) -> Allocator {
+    self.limit = Some(bytes);
+    self
+  }
+}
+
+/// Memory accounting of a Tidy instance created with an [`Allocator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryUsage {
+  /// Bytes currently allocated.
+  pub current: usize,
+  /// The highest number of bytes allocated at once.
+  pub peak: usize,
+  /// Number of allocations made.
+  pub allocations: usize,
+  /// The configured limit.
+  pub limit: Option<usize>,
+  /// Indicates whether or not an operation was abandoned because it would have exceeded the limit.
+  pub exceeded: bool,
+}
+
+struct RustGlobal;
+
+unsafe impl GlobalAlloc for RustGlobal {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    std::alloc::alloc(layout)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    std::alloc::dealloc(ptr, layout)
+  }
+
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    std::alloc::realloc(ptr, layout, new_size)
+  }
+}
+
+/// The TidyAllocator handed to tidyCreateWithAllocator, tidy passes it back to the vtbl functions.
+#[repr(C)]
+pub(crate) struct RustAllocator {
+  base: TidyAllocator,
+  alloc: Box<dyn GlobalAlloc + Send + Sync>,
+  limit: Option<usize>,
+  current: Cell<usize>,
+  peak: Cell<usize>,
+  allocations: Cell<usize>,
+  exceeded: Cell<bool>,
+  blocks: Cell<*mut Header>,
+}
+
+// Allocation failures and tidy's panics unwind to the guard around the current libtidy call, see panic::raise().
+static VTBL: TidyAllocatorVtbl = TidyAllocatorVtbl {
+  alloc: Some(tidy_alloc),
+  realloc: Some(tidy_realloc),
+  free: Some(tidy_free),
+  panic: Some(tidy_panic),
+};
+
+impl RustAllocator {
+  pub(crate) fn new(allocator: Allocator) -> RustAllocator {
+    RustAllocator {
+      base: TidyAllocator { vtbl: &VTBL },
+      alloc: allocator.alloc,
+      limit: allocator.limit,
+      current: Cell::new(0),
+      peak: Cell::new(0),
+      allocations: Cell::new(0),
+      exceeded: Cell::new(false),
+      blocks: Cell::new(std::ptr::null_mut()),
+    }
+  }
+
+  pub(crate) fn as_tidy_allocator(this: *mut RustAllocator) -> *mut TidyAllocator {
+    this as *mut TidyAllocator
+  }
+
+  pub(crate) fn usage(&self) -> MemoryUsage {
+    MemoryUsage {
+      current: self.current.get(),
+      peak: self.peak.get(),
+      allocations: self.allocations.get(),
+      limit: self.limit,
+      exceeded: self.exceeded.get(),
+    }
+  }
+
+  /// Free all blocks that are still allocated, for documents that can't be released through tidy.
+  pub(crate) unsafe fn release_all(&self) {
+    let mut block = self.blocks.replace(std::ptr::null_mut());
+    while !block.is_null() {
+      let next = (*block).next;
+      self.alloc.dealloc(block as *mut u8, layout((*block).size));
+      block = next;
+    }
+    self.current.set(0);
+  }
+
+  /// Abandon the current operation if resizing a block from old to new bytes would exceed the limit.
+  fn reserve(&self, old: usize, new: usize) {
+    if let Some(limit) = self.limit {
+      // Only guarded operations can be abandoned, other allocations are served regardless.
+      if (self.current.get() - old).saturating_add(new) > limit && panic::guarded() {
+        self.exceeded.set(true);
+        panic::exceed_limit(limit);
+      }
+    }
+  }
+
+  unsafe fn link(&self, block: *mut Header, size: usize) {
+    let next = self.blocks.get();
+    block.write(Header {
+      size,
+      prev: std::ptr::null_mut(),
+      next,
+    });
+    if !next.is_null() {
+      (*next).prev = block;
+    }
+    self.blocks.set(block);
+    self.current.set(self.current.get() + size);
+    if self.current.get() > self.peak.get() {
+      self.peak.set(self.current.get());
+    }
+  }
+
+  unsafe fn unlink(&self, block: *mut Header) {
+    let Header { size, prev, next } = block.read();
+    if prev.is_null() {
+      self.blocks.set(next);
+    } else {
+      (*prev).next = next;
+    }
+    if !next.is_null() {
+      (*next).prev = prev;
+    }
+    self.current.set(self.current.get() - size);
+  }
+}
+
+fn layout(size: usize) -> Layout {
+  match size.checked_add(HEADER) {
+    Some(total) => match Layout::from_size_align(total, ALIGN) {
+      Ok(layout) => layout,
+      Err(_) => oom(size),
+    },
+    None => oom(size),
+  }
+}
+
+fn oom(size: usize) -> ! {
+  panic::raise(format!("Out of memory allocating {} bytes", size))
+}
+
+unsafe extern "C-unwind" fn tidy_alloc(
+  base: *mut TidyAllocator,
+  size: tidy_sys::size_t,
+) -> *mut c_void {
+  let size = size as usize;
+  let allocator = &*(base as *const RustAllocator);
+  let layout = layout(size);
+  allocator.reserve(0, size);
+  let block = allocator.alloc.alloc(layout) as *mut Header;
+  if block.is_null() {
+    oom(size);
+  }
+  allocator.link(block, size);
+  allocator.allocations.set(allocator.allocations.get() + 1);
+  (block as *mut u8).add(HEADER) as *mut c_void
+}
+
+unsafe extern "C-unwind" fn tidy_realloc(
+  base: *mut TidyAllocator,
+  ptr: *mut c_void,
+  size: tidy_sys::size_t,
+) -> *mut c_void {
+  if ptr.is_null() {
+    return tidy_alloc(base, size);
+  }
+  let size = size as usize;
+  let allocator = &*(base as *const RustAllocator);
+  let old = (ptr as *mut u8).sub(HEADER) as *mut Header;
+  let old_size = (*old).size;
+  let new_layout = layout(size);
+  allocator.reserve(old_size, size);
+  allocator.unlink(old);
+  let block = allocator
+    .alloc
+    .realloc(old as *mut u8, layout(old_size), new_layout.size()) as *mut Header;
+  if block.is_null() {
+    // The old block is still valid and owned by tidy.
+    allocator.link(old, old_size);
+    oom(size);
+  }
+  allocator.link(block, size);
+  (block as *mut u8).add(HEADER) as *mut c_void
+}
+
+unsafe extern "C-unwind" fn tidy_free(base: *mut TidyAllocator, ptr: *mut c_void) {
+  if ptr.is_null() {
+    return;
+  }
+  let allocator = &*(base as *const RustAllocator);
+  let block = (ptr as *mut u8).sub(HEADER) as *mut Header;
+  let size = (*block).size;
+  allocator.unlink(block);
+  allocator.alloc.dealloc(block as *mut u8, layout(size));
+}
+
+unsafe extern "C-unwind" fn tidy_panic(_base: *mut TidyAllocator, msg: ctmbstr) {
+  panic::raise(TidyUtil::c_str_to_owned(msg))
+}
@@ -0,0 +1,156 @@
+use crate::{Node, Tidy, TidyAttrId, TidyUtil};
+use std::fmt;
+use tidy_sys::*;
+
+/// An attribute of a [`Node`], borrowed from the document that owns it.
+///
+/// ```
+/// # use tidy::*;
+/// # fn main() -> Result<(), TidyError> {
+/// let mut tidy = Tidy::new()?;
+/// tidy.parse_string(b"<a href='/home' onclick='go()'>Home</a>".to_vec())?;
+/// tidy.clean_and_repair()?;
+///
+/// let a = tidy.body().unwrap().first_child().unwrap();
+/// assert_eq!(a.attr("href").unwrap().value().as_deref(), Some("/home"));
+/// assert!(a.attr_by_id(TidyAttrId::TidyAttr_OnCLICK).unwrap().is_event());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct Attr<'doc> {
+  node: Node<'doc>,
+  pub(crate) tattr: TidyAttr,
+}
+
+impl<'doc> Attr<'doc> {
+  pub(crate) fn from_raw(node: Node<'doc>, tattr: TidyAttr) -> Option<Attr<'doc>> {
+    if tattr.is_null() {
+      None
+    } else {
+      Some(Attr { node, tattr })
+    }
+  }
+
+  /// Get the node this attribute belongs to.
+  pub fn node(&self) -> Node<'doc> {
+    self.node
+  }
+
+  /// Get the name of this attribute.
+  ///
+  /// # Returns
+  /// The attribute name as it appears in the repaired document.
+  pub fn name(&self) -> String {
+    unsafe { TidyUtil::c_str_to_owned(tidyAttrName(self.tattr)) }
+  }
+
+  /// Get the value of this attribute.
+  ///
+  /// # Returns
+  /// The attribute value, or None for attributes without a value, e.g. `<input disabled>`. Invalid UTF-8 sequences
+  /// are replaced with U+FFFD, use value_bytes to get the raw value.
+  pub fn value(&self) -> Option<String> {
+    self
+      .value_bytes()
+      .map(|val| String::from_utf8_lossy(&val).into_owned())
+  }
+
+  /// Get the raw value of this attribute.
+  ///
+  /// # Returns
+  /// The attribute value as bytes in tidy's internal encoding, or None for attributes without a value.
+  pub fn value_bytes(&self) -> Option<Vec<u8>> {
+    unsafe {
+      let val = tidyAttrValue(self.tattr);
+      if val.is_null() {
+        None
+      } else {
+        Some(TidyUtil::c_str_to_bytes(val))
+      }
+    }
+  }
+
+  /// Get the id of this attribute.
+  ///
+  /// # Returns
+  /// The TidyAttrId of this attribute, TidyAttr_UNKNOWN for attributes tidy doesn't know.
+  pub fn id(&self) -> TidyAttrId {
+    unsafe { tidyAttrGetId(self.tattr) }
+  }
+
+  /// Indicates whether or not this is an event handler attribute, e.g. onclick.
+  pub fn is_event(&self) -> bool {
+    unsafe { TidyUtil::tidy_bool_to_bool(tidyAttrIsEvent(self.tattr)) }
+  }
+}
+
+impl<'doc> PartialEq for Attr<'doc> {
+  fn eq(&self, other: &Self) -> bool {
+    self.tattr == other.tattr
+  }
+}
+
+impl<'doc> Eq for Attr<'doc> {}
+
+impl<'doc> fmt::Debug for Attr<'doc> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Attr")
+      .field("name", &self.name())
+      .field("value", &self.value())
+      .finish()
+  }
+}
+
+/// Iterator over the attributes of a node, see [`Node::attributes`].
+#[derive(Clone)]
+pub struct Attributes<'doc> {
+  pub(crate) next: Option<Attr<'doc>>,
+}
+
+impl<'doc> Iterator for Attributes<'doc> {
+  type Item = Attr<'doc>;
+
+  fn next(&mut self) -> Option<Attr<'doc>> {
+    let attr = self.next.take()?;
+    self.next = Attr::from_raw(attr.node, unsafe { tidyAttrNext(attr.tattr) });
+    Some(attr)
+  }
+}
+
+/// A mutable handle to an attribute, see [`NodeMut::attr`](crate::NodeMut::attr).
+pub struct AttrMut<'doc> {
+  tidy: &'doc mut Tidy,
+  tnod: TidyNode,
+  tattr: TidyAttr,
+}
+
+impl<'doc> AttrMut<'doc> {
+  pub(crate) fn from_raw(
+    tidy: &'doc mut Tidy,
+    tnod: TidyNode,
+    tattr: TidyAttr,
+  ) -> Option<AttrMut<'doc>> {
+    if tattr.is_null() {
+      None
+    } else {
+      Some(AttrMut { tidy, tnod, tattr })
+    }
+  }
+
+  /// Get a read-only handle to this attribute, valid until the tree is changed again.
+  pub fn attr(&self) -> Attr<'_> {
+    Attr::from_raw(Node::from_raw(self.tidy, self.tnod).unwrap(), self.tattr).unwrap()
+  }
+
+  /// Remove this attribute from its node.
+  pub fn discard(self) {
+    unsafe { tidyAttrDiscard(self.tidy.tdoc, self.tnod, self.tattr) }
+  }
+}
+
+impl<'doc> fmt::Debug for AttrMut<'doc> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_tuple("AttrMut").field(&self.attr()).finish()
+  }
+}
@@ -0,0 +1,130 @@
+use libc::*;
+use std::fmt;
+use std::ops::Deref;
+use tidy_sys::*;
+
+/// An owned tidy byte buffer.
+///
+/// Wraps a heap allocated TidyBuffer, so its address stays stable while tidy writes to it. The memory is released when
+/// the buffer is dropped.
+///
+/// ```
+/// # use tidy::*;
+/// let mut buf = Buffer::new();
+/// buf.append(b"<p>Hello");
+/// buf.append(b"</p>");
+/// assert_eq!(&buf[..], b"<p>Hello</p>");
+/// assert_eq!(buf.into_vec(), b"<p>Hello</p>".to_vec());
+/// ```
+pub struct Buffer {
+  buf: *mut TidyBuffer,
+}
+
+impl Buffer {
+  /// Create an empty buffer using tidy's default allocator.
+  pub fn new() -> Buffer {
+    let buf = Box::into_raw(Box::new(TidyBuffer::default()));
+    unsafe { tidyBufInit(buf) };
+    Buffer { buf }
+  }
+
+  /// Create an empty buffer using a custom allocator, which must outlive the buffer.
+  pub(crate) unsafe fn with_allocator(allocator: *mut TidyAllocator) -> Buffer {
+    let buf = Box::into_raw(Box::new(TidyBuffer::default()));
+    tidyBufInitWithAllocator(buf, allocator);
+    Buffer { buf }
+  }
+
+  /// Get the contents of the buffer.
+  pub fn as_bytes(&self) -> &[u8] {
+    unsafe {
+      let buf = &*self.buf;
+      if buf.bp.is_null() {
+        &[]
+      } else {
+        std::slice::from_raw_parts(buf.bp, buf.size as usize)
+      }
+    }
+  }
+
+  /// Append bytes to the buffer, growing it as necessary.
+  pub fn append(&mut self, bytes: &[u8]) {
+    for chunk in bytes.chunks(c_uint::MAX as usize) {
+      unsafe {
+        tidyBufAppend(
+          self.buf,
+          chunk.as_ptr() as *mut c_void,
+          chunk.len() as c_uint,
+        )
+      };
+    }
+  }
+
+  /// Remove the contents of the buffer, keeping the allocated memory.
+  pub fn clear(&mut self) {
+    unsafe { tidyBufClear(self.buf) }
+  }
+
+  /// Copy the contents of the buffer into a vector, releasing the buffer.
+  pub fn into_vec(self) -> Vec<u8> {
+    self.as_bytes().to_vec()
+  }
+
+  /// Get the contents of the buffer as a string, replacing invalid UTF-8 sequences with U+FFFD.
+  pub fn to_string_lossy(&self) -> String {
+    String::from_utf8_lossy(self.as_bytes()).into_owned()
+  }
+
+  pub(crate) fn as_ptr(&self) -> *mut TidyBuffer {
+    self.buf
+  }
+}
+
+// The TidyBuffer is owned exclusively, shared references only read it.
+unsafe impl Send for Buffer {}
+unsafe impl Sync for Buffer {}
+
+impl Default for Buffer {
+  fn default() -> Buffer {
+    Buffer::new()
+  }
+}
+
+impl Clone for Buffer {
+  fn clone(&self) -> Buffer {
+    let mut buf = Buffer::new();
+    buf.append(self.as_bytes());
+    buf
+  }
+}
+
+impl Deref for Buffer {
+  type Target = [u8];
+
+  fn deref(&self) -> &[u8] {
+    self.as_bytes()
+  }
+}
+
+impl AsRef<[u8]> for Buffer {
+  fn as_ref(&self) -> &[u8] {
+    self.as_bytes()
+  }
+}
+
+impl fmt::Debug for Buffer {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_tuple("Buffer")
+      .field(&String::from_utf8_lossy(self.as_bytes()))
+      .finish()
+  }
+}
+
+impl Drop for Buffer {
+  fn drop(&mut self) {
+    unsafe {
+      tidyBufFree(self.buf);
+      drop(Box::from_raw(self.buf));
+    }
+  }
+}
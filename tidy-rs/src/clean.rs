@@ -0,0 +1,127 @@
+use crate::sanitize::Allowlist;
+use crate::{Diagnostic, SourceMap, Tidy, TidyConfig, TidyError, TidyOptionId, TidySeverity};
+
+/// The result of a one-shot tidy run, see [`clean_html`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cleaned {
+  /// The cleaned document, empty if errors suppressed the output.
+  pub output: Vec<u8>,
+  /// The messages tidy emitted while processing the document.
+  pub diagnostics: Vec<Diagnostic>,
+  /// The status of the document after diagnostics.
+  pub status: TidySeverity,
+  /// Where the nodes of the input were written to in the output.
+  pub source_map: SourceMap,
+}
+
+impl Cleaned {
+  /// Get the cleaned document as a string, replacing invalid UTF-8 sequences with U+FFFD.
+  pub fn output_string(&self) -> String {
+    String::from_utf8_lossy(&self.output).into_owned()
+  }
+}
+
+/// Parse, clean and repair an HTML document in one go.
+///
+/// Applies the config, parses the input as UTF-8, runs clean and repair as well as diagnostics and saves the result.
+///
+/// # Returns
+/// The cleaned document together with the diagnostics and the final status, or a TidyError if a step failed.
+/// # Parameters
+/// **html**	The document to tidy.
+/// **config**	The options to apply.
+/// **force_output**	Whether or not to write the output if tidy found errors.
+///
+/// ```
+/// # use tidy::*;
+/// # fn main() -> Result<(), TidyError> {
+/// let config: TidyConfig = vec![("show-body-only", "yes")].into_iter().collect();
+/// let cleaned = clean_html("<p>Unclosed <b>bold", &config, true)?;
+/// assert!(cleaned.output_string().contains("<p>Unclosed <b>bold</b></p>"));
+/// assert_eq!(cleaned.status, TidySeverity::Warning);
+/// assert!(cleaned.diagnostics.iter().any(|d| d.key == "MISSING_ENDTAG_FOR"));
+///
+/// let failed = clean_html("<p>Unknown <foo>element", &config, false)?;
+/// assert!(failed.output.is_empty() && failed.status == TidySeverity::Error);
+/// # Ok(())
+/// # }
+/// ```
+pub fn clean_html(
+  html: &str,
+  config: &TidyConfig,
+  force_output: bool,
+) -> Result<Cleaned, TidyError> {
+  run(
+    html.as_bytes(),
+    config,
+    false,
+    Some("utf8"),
+    force_output,
+    None,
+  )
+}
+
+/// Parse, clean and repair an XML document in one go.
+///
+/// Like [`clean_html`], with TidyXmlTags enabled.
+pub fn clean_xml(xml: &str, config: &TidyConfig, force_output: bool) -> Result<Cleaned, TidyError> {
+  run(
+    xml.as_bytes(),
+    config,
+    true,
+    Some("utf8"),
+    force_output,
+    None,
+  )
+}
+
+/// Parse, clean and repair a document given as bytes in one go.
+///
+/// The input encoding is taken from the config.
+///
+/// # Parameters
+/// **input**	The document to tidy.
+/// **config**	The options to apply.
+/// **force_output**	Whether or not to write the output if tidy found errors.
+pub fn clean(input: &[u8], config: &TidyConfig, force_output: bool) -> Result<Cleaned, TidyError> {
+  run(input, config, false, None, force_output, None)
+}
+
+pub(crate) fn run(
+  input: &[u8],
+  config: &TidyConfig,
+  xml: bool,
+  in_encoding: Option<&str>,
+  force_output: bool,
+  allowlist: Option<&Allowlist>,
+) -> Result<Cleaned, TidyError> {
+  let mut tidy = Tidy::new()?;
+  if xml {
+    tidy.opt_set_bool(TidyOptionId::TidyXmlTags, true)?;
+  }
+  tidy.apply_config(config)?;
+  if allowlist.is_some() {
+    // Write character references in their canonical form, not as they were found in the input.
+    tidy.opt_set_bool(TidyOptionId::TidyPreserveEntities, false)?;
+    tidy.opt_set_bool(TidyOptionId::TidyQuoteAmpersand, true)?;
+  }
+  if let Some(encnam) = in_encoding {
+    tidy.set_in_char_encoding(encnam)?;
+  }
+  tidy.parse_bytes(input)?;
+  tidy.clean_and_repair()?;
+  if let Some(allowlist) = allowlist {
+    tidy.sanitize(allowlist);
+  }
+  tidy.run_diagnostics()?;
+  if force_output && tidy.error_count() > 0 {
+    tidy.opt_set_bool(TidyOptionId::TidyForceOutput, true)?;
+  }
+  let (_, source_map) = tidy.save_buffer_with_source_map()?;
+  Ok(Cleaned {
+    output: tidy.take_output().into_vec(),
+    diagnostics: tidy.take_diagnostics(),
+    status: tidy.status(),
+    source_map,
+  })
+}
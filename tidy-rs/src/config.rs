@@ -0,0 +1,291 @@
+use crate::{
+  panic, Tidy, TidyConfigCategory, TidyContext, TidyOption, TidyOptionId, TidyOptionType, TidyUtil,
+};
+use std::collections::btree_map;
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use tidy_sys::*;
+
+/// A snapshot of tidy option values, keyed by option name.
+///
+/// Values use the same textual representation as tidy config files, e.g. `indent = auto` or `wrap = 80`. With the
+/// `serde` feature enabled the snapshot (de)serializes as a plain map, so it can be embedded in TOML, JSON or YAML
+/// application configs.
+///
+/// ```
+/// # use tidy::*;
+/// # fn main() -> Result<(), TidyError> {
+/// let tidy = Tidy::new()?;
+/// tidy.opt_parse_value("indent", "auto")?;
+/// let config = tidy.config();
+/// assert_eq!(config.get("indent"), Some("auto"));
+///
+/// let other = Tidy::new()?;
+/// other.apply_config(&config)?;
+/// assert_eq!(other.config(), config);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct TidyConfig {
+  values: BTreeMap<String, String>,
+}
+
+impl TidyConfig {
+  /// Create an empty snapshot.
+  pub fn new() -> TidyConfig {
+    Default::default()
+  }
+
+  /// Get the value of the named option.
+  pub fn get(&self, optnam: &str) -> Option<&str> {
+    self.values.get(optnam).map(String::as_str)
+  }
+
+  /// Set the value of the named option, returning the previous value.
+  pub fn insert<N: Into<String>, V: Into<String>>(&mut self, optnam: N, val: V) -> Option<String> {
+    self.values.insert(optnam.into(), val.into())
+  }
+
+  /// Remove the named option, returning its value.
+  pub fn remove(&mut self, optnam: &str) -> Option<String> {
+    self.values.remove(optnam)
+  }
+
+  /// Iterate over option names and values, ordered by name.
+  pub fn iter(&self) -> btree_map::Iter<'_, String, String> {
+    self.values.iter()
+  }
+
+  /// Get the number of options in the snapshot.
+  pub fn len(&self) -> usize {
+    self.values.len()
+  }
+
+  /// Indicates whether or not the snapshot is empty.
+  pub fn is_empty(&self) -> bool {
+    self.values.is_empty()
+  }
+}
+
+impl From<BTreeMap<String, String>> for TidyConfig {
+  fn from(values: BTreeMap<String, String>) -> TidyConfig {
+    TidyConfig { values }
+  }
+}
+
+impl From<TidyConfig> for BTreeMap<String, String> {
+  fn from(config: TidyConfig) -> BTreeMap<String, String> {
+    config.values
+  }
+}
+
+impl<N: Into<String>, V: Into<String>> FromIterator<(N, V)> for TidyConfig {
+  fn from_iter<I: IntoIterator<Item = (N, V)>>(iter: I) -> TidyConfig {
+    TidyConfig {
+      values: iter
+        .into_iter()
+        .map(|(n, v)| (n.into(), v.into()))
+        .collect(),
+    }
+  }
+}
+
+impl IntoIterator for TidyConfig {
+  type Item = (String, String);
+  type IntoIter = btree_map::IntoIter<String, String>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.values.into_iter()
+  }
+}
+
+impl<'a> IntoIterator for &'a TidyConfig {
+  type Item = (&'a String, &'a String);
+  type IntoIter = btree_map::Iter<'a, String, String>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.values.iter()
+  }
+}
+
+/// Metadata describing a tidy option, see [`Tidy::options`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionInfo {
+  /// The option id.
+  pub id: TidyOptionId,
+  /// The option name as used in config files, e.g. `indent-spaces`.
+  pub name: String,
+  /// The category the option is sorted into.
+  pub category: TidyConfigCategory,
+  /// The datatype of the option.
+  pub opt_type: TidyOptionType,
+  /// The default value in config file representation, None for string options without a default.
+  pub default: Option<String>,
+  /// The current value in config file representation, None for string options without a value.
+  pub value: Option<String>,
+  /// Indicates whether or not the option is read-only, i.e. for internal use.
+  pub read_only: bool,
+  /// Indicates whether or not the option takes a list of values, e.g. tag names.
+  pub is_list: bool,
+  /// The values the option accepts, empty if it isn't restricted to a pick list.
+  pub picks: Vec<String>,
+  /// The description of the option.
+  pub doc: Option<String>,
+  /// Names of related options the description refers to.
+  pub doc_links: Vec<String>,
+}
+
+impl OptionInfo {
+  pub(crate) fn new(tidy: &Tidy, opt: TidyOption) -> OptionInfo {
+    unsafe {
+      let picks = opt_picks(opt);
+      let opt_type = tidyOptGetType(opt);
+      let default = match opt_type {
+        TidyOptionType::TidyString => {
+          let val = tidyOptGetDefault(opt);
+          if val.is_null() {
+            None
+          } else {
+            Some(TidyUtil::c_str_to_owned(val))
+          }
+        }
+        TidyOptionType::TidyBoolean => Some(bool_to_opt_value(tidyOptGetDefaultBool(opt))),
+        TidyOptionType::TidyInteger => {
+          let val = tidyOptGetDefaultInt(opt);
+          match picks.get(val as usize) {
+            Some(pick) => Some(pick.clone()),
+            None => Some(val.to_string()),
+          }
+        }
+      };
+      let doc = tidyOptGetDoc(tidy.tdoc, opt);
+      let mut doc_links = Vec::new();
+      let mut iter = tidyOptGetDocLinksList(tidy.tdoc, opt);
+      while !iter.is_null() {
+        let link = tidyOptGetNextDocLinks(tidy.tdoc, &mut iter);
+        if !link.is_null() {
+          doc_links.push(TidyUtil::c_str_to_owned(tidyOptGetName(link)));
+        }
+      }
+      OptionInfo {
+        id: tidyOptGetId(opt),
+        name: TidyUtil::c_str_to_owned(tidyOptGetName(opt)),
+        category: tidyOptGetCategory(opt),
+        opt_type,
+        default,
+        value: opt_value_string(tidy, opt),
+        read_only: TidyUtil::tidy_bool_to_bool(tidyOptIsReadOnly(opt)),
+        is_list: TidyUtil::tidy_bool_to_bool(tidyOptionIsList(opt)),
+        picks,
+        doc: if doc.is_null() {
+          None
+        } else {
+          Some(TidyUtil::c_str_to_owned(doc))
+        },
+        doc_links,
+      }
+    }
+  }
+}
+
+unsafe fn opt_picks(opt: TidyOption) -> Vec<String> {
+  let mut picks = Vec::new();
+  let mut iter = tidyOptGetPickList(opt);
+  while !iter.is_null() {
+    let pick = tidyOptGetNextPick(opt, &mut iter);
+    if !pick.is_null() {
+      picks.push(TidyUtil::c_str_to_owned(pick));
+    }
+  }
+  picks
+}
+
+fn bool_to_opt_value(val: Bool) -> String {
+  String::from(if TidyUtil::tidy_bool_to_bool(val) {
+    "yes"
+  } else {
+    "no"
+  })
+}
+
+/// Get the current value of an option in its config file representation.
+pub(crate) fn opt_value_string(tidy: &Tidy, opt: TidyOption) -> Option<String> {
+  unsafe {
+    let optid = tidyOptGetId(opt);
+    match tidyOptGetType(opt) {
+      TidyOptionType::TidyString => {
+        let val = tidyOptGetValue(tidy.tdoc, optid);
+        if val.is_null() {
+          None
+        } else {
+          Some(TidyUtil::c_str_to_owned(val))
+        }
+      }
+      TidyOptionType::TidyBoolean => Some(bool_to_opt_value(tidyOptGetBool(tidy.tdoc, optid))),
+      TidyOptionType::TidyInteger => {
+        let pick = match optid {
+          TidyOptionId::TidyCharEncoding
+          | TidyOptionId::TidyInCharEncoding
+          | TidyOptionId::TidyOutCharEncoding => tidyOptGetEncName(tidy.tdoc, optid),
+          _ if tidyOptGetPickList(opt).is_null() => std::ptr::null(),
+          _ => tidyOptGetCurrPick(tidy.tdoc, optid),
+        };
+        if pick.is_null() {
+          Some(tidyOptGetInt(tidy.tdoc, optid).to_string())
+        } else {
+          Some(TidyUtil::c_str_to_owned(pick))
+        }
+      }
+    }
+  }
+}
+
+/// Closure handling options tidy doesn't know, see [`Tidy::set_config_callback`].
+pub type ConfigCallback = Box<dyn FnMut(&str, &str) -> bool + Send>;
+
+/// Closure notified about option changes, see [`Tidy::set_config_change_callback`].
+pub type ConfigChangeCallback = Box<dyn FnMut(TidyOptionId, &str) + Send>;
+
+pub(crate) unsafe extern "C" fn config_callback(
+  tdoc: TidyDoc,
+  option: ctmbstr,
+  value: ctmbstr,
+) -> Bool {
+  let context = tidyGetAppData(tdoc) as *const TidyContext;
+  if context.is_null() {
+    return Bool_no;
+  }
+  let handled = match (*context).config_callback.try_borrow_mut() {
+    Ok(mut callback) => match callback.as_mut() {
+      Some(callback) => panic::catch(&(*context).callback_panic, false, || {
+        callback(
+          &TidyUtil::c_str_to_owned(option),
+          &TidyUtil::c_str_to_owned(value),
+        )
+      }),
+      None => false,
+    },
+    Err(_) => false,
+  };
+  TidyUtil::bool_to_tidy_bool(handled)
+}
+
+pub(crate) unsafe extern "C" fn config_change_callback(tdoc: TidyDoc, option: TidyOption) {
+  let context = tidyGetAppData(tdoc) as *const TidyContext;
+  if context.is_null() {
+    return;
+  }
+  if let Ok(mut callback) = (*context).config_change_callback.try_borrow_mut() {
+    if let Some(callback) = callback.as_mut() {
+      panic::catch(&(*context).callback_panic, (), || {
+        callback(
+          tidyOptGetId(option),
+          &TidyUtil::c_str_to_owned(tidyOptGetName(option)),
+        )
+      });
+    }
+  }
+}
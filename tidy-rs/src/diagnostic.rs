@@ -0,0 +1,119 @@
+use crate::{panic, TidyContext, TidyReportLevel, TidyUtil};
+use libc::*;
+use std::fmt;
+use tidy_sys::*;
+
+/// An argument that was substituted into the format string of a [`Diagnostic`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MessageArgument {
+  Int(c_int),
+  UInt(c_uint),
+  String(String),
+  Double(f64),
+  Unknown,
+}
+
+/// A single report or dialogue message emitted by tidy.
+///
+/// Diagnostics are collected on the [`Tidy`](crate::Tidy) instance that emitted them, see
+/// [`Tidy::diagnostics`](crate::Tidy::diagnostics).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+  /// The numeric message code.
+  pub code: c_uint,
+  /// The message key, e.g. `MISSING_ENDTAG_FOR`, as used by the mute option.
+  pub key: String,
+  /// Line of the offending markup, 0 if the message doesn't refer to a position.
+  pub line: c_int,
+  /// Column of the offending markup, 0 if the message doesn't refer to a position.
+  pub column: c_int,
+  /// Severity of the message.
+  pub level: TidyReportLevel,
+  /// The formatted message in the current language, without position and level prefix.
+  pub message: String,
+  /// The complete message in the current language as written to the error buffer.
+  pub output: String,
+  /// The format string the message was built from.
+  pub format: String,
+  /// The formatted message in English, see [`Tidy::set_language`](crate::Tidy::set_language).
+  pub default_message: String,
+  /// The complete message in English.
+  pub default_output: String,
+  /// Indicates whether or not the message was muted by the mute option.
+  pub muted: bool,
+  /// Arguments that were substituted into the format string.
+  pub arguments: Vec<MessageArgument>,
+}
+
+impl Diagnostic {
+  unsafe fn from_message(tmessage: TidyMessage) -> Diagnostic {
+    Diagnostic {
+      code: tidyGetMessageCode(tmessage),
+      key: TidyUtil::c_str_to_owned(tidyGetMessageKey(tmessage)),
+      line: tidyGetMessageLine(tmessage),
+      column: tidyGetMessageColumn(tmessage),
+      level: tidyGetMessageLevel(tmessage),
+      message: TidyUtil::c_str_to_owned(tidyGetMessage(tmessage)),
+      output: TidyUtil::c_str_to_owned(tidyGetMessageOutput(tmessage)),
+      format: TidyUtil::c_str_to_owned(tidyGetMessageFormat(tmessage)),
+      default_message: TidyUtil::c_str_to_owned(tidyGetMessageDefault(tmessage)),
+      default_output: TidyUtil::c_str_to_owned(tidyGetMessageOutputDefault(tmessage)),
+      muted: TidyUtil::tidy_bool_to_bool(tidyGetMessageIsMuted(tmessage)),
+      arguments: message_arguments(tmessage),
+    }
+  }
+}
+
+impl fmt::Display for Diagnostic {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.output.trim_end())
+  }
+}
+
+unsafe fn message_arguments(tmessage: TidyMessage) -> Vec<MessageArgument> {
+  let mut arguments = Vec::new();
+  let mut iter = tidyGetMessageArguments(tmessage);
+  while !iter.is_null() {
+    let mut arg = tidyGetNextMessageArgument(tmessage, &mut iter);
+    let argument = match tidyGetArgType(tmessage, &mut arg) {
+      TidyFormatParameterType::tidyFormatType_INT => {
+        MessageArgument::Int(tidyGetArgValueInt(tmessage, &mut arg))
+      }
+      TidyFormatParameterType::tidyFormatType_UINT => {
+        MessageArgument::UInt(tidyGetArgValueUInt(tmessage, &mut arg))
+      }
+      TidyFormatParameterType::tidyFormatType_STRING => MessageArgument::String(
+        TidyUtil::c_str_to_owned(tidyGetArgValueString(tmessage, &mut arg)),
+      ),
+      TidyFormatParameterType::tidyFormatType_DOUBLE => {
+        MessageArgument::Double(tidyGetArgValueDouble(tmessage, &mut arg))
+      }
+      _ => MessageArgument::Unknown,
+    };
+    arguments.push(argument);
+  }
+  arguments
+}
+
+/// Closure deciding whether a message is emitted, see [`Tidy::set_report_filter`](crate::Tidy::set_report_filter).
+pub type ReportFilter = Box<dyn FnMut(&Diagnostic) -> bool + Send>;
+
+pub(crate) unsafe extern "C" fn message_callback(tmessage: TidyMessage) -> Bool {
+  let tdoc = tidyGetMessageDoc(tmessage);
+  let context = tidyGetAppData(tdoc) as *const TidyContext;
+  if context.is_null() {
+    return Bool_yes;
+  }
+  let diagnostic = Diagnostic::from_message(tmessage);
+  let emit = match (*context).report_filter.try_borrow_mut() {
+    Ok(mut filter) => match filter.as_mut() {
+      Some(filter) => panic::catch(&(*context).callback_panic, true, || filter(&diagnostic)),
+      None => true,
+    },
+    Err(_) => true,
+  };
+  if emit {
+    (*context).diagnostics.borrow_mut().push(diagnostic);
+  }
+  TidyUtil::bool_to_tidy_bool(emit)
+}
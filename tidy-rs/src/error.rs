@@ -0,0 +1,223 @@
+use crate::{Diagnostic, TidyOptionId, TidySeverity};
+use libc::*;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::sync::Arc;
+
+/// Error returned by the operations of [`Tidy`](crate::Tidy).
+///
+/// Each variant names the failing operation and carries what was passed to it, the status code tidy returned where
+/// there is one, and the diagnostics tidy emitted while the operation ran.
+#[derive(Debug, Clone)]
+pub enum TidyError {
+  /// Setting up a new document failed.
+  Create { status: c_int },
+  /// Parsing the input document failed.
+  Parse {
+    /// The file that was parsed, None for other inputs.
+    path: Option<String>,
+    status: c_int,
+    diagnostics: Vec<Diagnostic>,
+  },
+  /// Cleaning and repairing the parsed document failed.
+  Repair {
+    status: c_int,
+    diagnostics: Vec<Diagnostic>,
+  },
+  /// Running diagnostics on the document failed.
+  Diagnose {
+    status: c_int,
+    diagnostics: Vec<Diagnostic>,
+  },
+  /// Loading, saving, copying or restoring the configuration failed.
+  Config {
+    /// The config file that was loaded or saved, None for other operations.
+    path: Option<String>,
+    /// The status tidy returned, None for operations that only report success or failure.
+    status: Option<c_int>,
+    diagnostics: Vec<Diagnostic>,
+  },
+  /// Setting a character encoding failed.
+  Encoding {
+    /// The encoding name that was rejected.
+    name: String,
+    status: c_int,
+    diagnostics: Vec<Diagnostic>,
+  },
+  /// The requested language isn't installed.
+  Language {
+    /// The rejected language code.
+    code: String,
+  },
+  /// Setting or resetting an option failed.
+  Option {
+    /// The option id, None if the option was given by name or all options were reset.
+    id: Option<TidyOptionId>,
+    /// The option name, None if it isn't known.
+    name: Option<String>,
+    /// The value that was rejected, None for resets.
+    value: Option<String>,
+    diagnostics: Vec<Diagnostic>,
+  },
+  /// Saving the document failed.
+  Save {
+    /// The file that was written, None for other outputs.
+    path: Option<String>,
+    status: c_int,
+    diagnostics: Vec<Diagnostic>,
+  },
+  /// An allocation would have exceeded the memory limit of the document's allocator, see
+  /// [`Allocator::limit`](crate::Allocator::limit). The instance can't be used anymore.
+  MemoryLimit {
+    /// The configured limit in bytes.
+    limit: usize,
+    diagnostics: Vec<Diagnostic>,
+  },
+  /// libtidy panicked, e.g. because an allocation failed. The instance can't be used anymore.
+  Panic {
+    /// The panic message.
+    message: String,
+    diagnostics: Vec<Diagnostic>,
+  },
+  /// A string argument contained a NUL byte, which can't be passed to tidy.
+  Nul {
+    /// The rejected argument.
+    value: String,
+    /// Byte position of the first NUL.
+    position: usize,
+  },
+  /// A file couldn't be opened or written.
+  Io {
+    /// The file that couldn't be accessed, None if the error didn't concern a file.
+    path: Option<String>,
+    error: Arc<io::Error>,
+  },
+}
+
+impl TidyError {
+  /// Get the status code tidy returned.
+  ///
+  /// # Returns
+  /// The status code, or None if the failing operation doesn't return one.
+  pub fn status(&self) -> Option<c_int> {
+    match self {
+      TidyError::Create { status }
+      | TidyError::Parse { status, .. }
+      | TidyError::Repair { status, .. }
+      | TidyError::Diagnose { status, .. }
+      | TidyError::Encoding { status, .. }
+      | TidyError::Save { status, .. } => Some(*status),
+      TidyError::Config { status, .. } => *status,
+      TidyError::Language { .. }
+      | TidyError::Option { .. }
+      | TidyError::MemoryLimit { .. }
+      | TidyError::Panic { .. }
+      | TidyError::Nul { .. }
+      | TidyError::Io { .. } => None,
+    }
+  }
+
+  /// Get the severity of the error.
+  ///
+  /// # Returns
+  /// TidySeverity::Warning or TidySeverity::Error for the corresponding tidy status codes, TidySeverity::Severe
+  /// otherwise.
+  pub fn severity(&self) -> TidySeverity {
+    match self.status() {
+      Some(1) => TidySeverity::Warning,
+      Some(2) => TidySeverity::Error,
+      _ => TidySeverity::Severe,
+    }
+  }
+
+  /// Get the diagnostics tidy emitted while the failing operation ran.
+  pub fn diagnostics(&self) -> &[Diagnostic] {
+    match self {
+      TidyError::Parse { diagnostics, .. }
+      | TidyError::Repair { diagnostics, .. }
+      | TidyError::Diagnose { diagnostics, .. }
+      | TidyError::Config { diagnostics, .. }
+      | TidyError::Encoding { diagnostics, .. }
+      | TidyError::Option { diagnostics, .. }
+      | TidyError::Save { diagnostics, .. }
+      | TidyError::MemoryLimit { diagnostics, .. }
+      | TidyError::Panic { diagnostics, .. } => diagnostics,
+      TidyError::Create { .. }
+      | TidyError::Language { .. }
+      | TidyError::Nul { .. }
+      | TidyError::Io { .. } => &[],
+    }
+  }
+
+  pub(crate) fn io(path: &str, status: c_int) -> TidyError {
+    TidyError::Io {
+      path: Some(path.to_string()),
+      error: Arc::new(io::Error::from_raw_os_error(-status)),
+    }
+  }
+}
+
+impl fmt::Display for TidyError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      TidyError::Create { status } => write!(f, "Tidy create error (status {})", status),
+      TidyError::Parse { path, status, .. } => match path {
+        Some(path) => write!(f, "Tidy parse error in {} (status {})", path, status),
+        None => write!(f, "Tidy parse error (status {})", status),
+      },
+      TidyError::Repair { status, .. } => {
+        write!(f, "Tidy clean and repair error (status {})", status)
+      }
+      TidyError::Diagnose { status, .. } => {
+        write!(f, "Tidy run diagnostics error (status {})", status)
+      }
+      TidyError::Config { path, .. } => match path {
+        Some(path) => write!(f, "Tidy config error in {}", path),
+        None => write!(f, "Tidy config error"),
+      },
+      TidyError::Encoding { name, .. } => write!(f, "Tidy char encoding error: {}", name),
+      TidyError::Language { code } => write!(f, "Tidy language error: {}", code),
+      TidyError::Option {
+        id, name, value, ..
+      } => {
+        write!(f, "Tidy option error")?;
+        match (name, id) {
+          (Some(name), _) => write!(f, ": {}", name)?,
+          (None, Some(id)) => write!(f, ": {:?}", id)?,
+          (None, None) => (),
+        }
+        match value {
+          Some(value) => write!(f, " = {}", value),
+          None => Ok(()),
+        }
+      }
+      TidyError::Save { path, status, .. } => match path {
+        Some(path) => write!(f, "Tidy save error in {} (status {})", path, status),
+        None => write!(f, "Tidy save error (status {})", status),
+      },
+      TidyError::MemoryLimit { limit, .. } => {
+        write!(f, "Tidy memory limit of {} bytes exceeded", limit)
+      }
+      TidyError::Panic { message, .. } => write!(f, "Tidy panic: {}", message),
+      TidyError::Nul { value, position } => write!(
+        f,
+        "Tidy argument contains a NUL byte at position {}: {:?}",
+        position, value
+      ),
+      TidyError::Io { path, error } => match path {
+        Some(path) => write!(f, "Tidy I/O error in {}: {}", path, error),
+        None => write!(f, "Tidy I/O error: {}", error),
+      },
+    }
+  }
+}
+
+impl Error for TidyError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      TidyError::Io { error, .. } => Some(error.as_ref()),
+      _ => None,
+    }
+  }
+}
@@ -5,7 +5,7 @@
 //!
 //! # pub fn main() -> Result<(), Box<dyn Error>> {
 //! let xml = "<test>5 < 6 and 9 > 7</test>";
-//! let tidy = Tidy::new()?;
+//! let mut tidy = Tidy::new()?;
 //! tidy.opt_set_bool(TidyOptionId::TidyXmlTags, true)?;
 //! tidy.set_char_encoding("utf8")?;
 //!
@@ -23,8 +23,8 @@
 //! }
 //!
 //! tidy.save_buffer()?;
-//! println!("\nDiagnostics:\n\n {}", TidyUtil::errbuf_as_string(&tidy));
-//! print!("{}", String::from_utf8_lossy(&TidyUtil::output_as_vector(&tidy).unwrap()));
+//! println!("\nDiagnostics:\n\n {}", tidy.errbuf().to_string_lossy());
+//! print!("{}", tidy.output().to_string_lossy());
 //! # Ok(())
 //! # }
 //! ```
@@ -35,22 +35,66 @@
 
 extern crate tidy_sys;
 
+use alloc::RustAllocator;
 use libc::*;
+use opt::{OptValue, TidyOpt};
 use std::boxed::Box;
-use std::error::Error;
+use std::cell::RefCell;
 use std::ffi::CStr;
 use std::ffi::CString;
 use std::fmt;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use stream::{ReaderSource, WriterSink};
 use tidy_sys::*;
 
+mod alloc;
+mod attr;
+mod buffer;
+mod clean;
+mod config;
+mod diagnostic;
+mod error;
+mod node;
+pub mod opt;
+mod panic;
+pub mod phase;
+mod pool;
+pub mod sanitize;
+mod source_map;
+mod stream;
+
+pub use alloc::{Allocator, MemoryUsage};
+pub use attr::*;
+pub use buffer::Buffer;
+pub use clean::{clean, clean_html, clean_xml, Cleaned};
+pub use config::{ConfigCallback, ConfigChangeCallback, OptionInfo, TidyConfig};
+pub use diagnostic::*;
+pub use error::TidyError;
+pub use node::*;
+pub use pool::{PooledTidy, TidyPool};
+pub use source_map::{SourceMap, SourceMapping};
+
 /// See [Html tidy docs](http://api.html-tidy.org/tidy/tidylib_api_5.6.0/group__public__enumerations.html)
 pub type TidyOptionId = tidy_sys::TidyOptionId;
 /// See [Html tidy docs](http://api.html-tidy.org/tidy/tidylib_api_5.6.0/group__public__enumerations.html)
 pub type TidyOption = tidy_sys::TidyOption;
 /// See [Html tidy docs](http://api.html-tidy.org/tidy/tidylib_api_5.6.0/group__public__enumerations.html)
 pub type TidyConfigCategory = tidy_sys::TidyConfigCategory;
+/// See [Html tidy docs](http://api.html-tidy.org/tidy/tidylib_api_5.6.0/group__public__enumerations.html)
+pub type TidyOptionType = tidy_sys::TidyOptionType;
+/// See [Html tidy docs](http://api.html-tidy.org/tidy/tidylib_api_5.6.0/group__public__enumerations.html)
+pub type TidyNodeType = tidy_sys::TidyNodeType;
+/// See [Html tidy docs](http://api.html-tidy.org/tidy/tidylib_api_5.6.0/group__public__enumerations.html)
+pub type TidyTagId = tidy_sys::TidyTagId;
+/// See [Html tidy docs](http://api.html-tidy.org/tidy/tidylib_api_5.6.0/group__public__enumerations.html)
+pub type TidyAttrId = tidy_sys::TidyAttrId;
+/// See [Html tidy docs](http://api.html-tidy.org/tidy/tidylib_api_5.6.0/group__public__enumerations.html)
+pub type TidyReportLevel = tidy_sys::TidyReportLevel;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TidySeverity {
   Success,
   Warning,
@@ -64,27 +108,24 @@ impl fmt::Display for TidySeverity {
   }
 }
 
-#[allow(dead_code)]
-#[derive(Debug, Clone)]
-pub struct TidyError {
-  severity: TidySeverity,
-  message: String,
-}
-
-impl std::fmt::Display for TidyError {
-  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-    write!(f, "{}", self.message)
-  }
-}
-
-impl Error for TidyError {}
-
 pub struct TidyUtil;
 impl TidyUtil {
+  /// Copy a C string returned by tidy into a String.
+  ///
+  /// Invalid UTF-8 sequences are replaced with U+FFFD, a null pointer results in an empty string.
   pub fn c_str_to_owned(in_str: ctmbstr) -> String {
+    String::from_utf8_lossy(&TidyUtil::c_str_to_bytes(in_str)).into_owned()
+  }
+
+  /// Copy a C string returned by tidy into a byte vector, without the terminating NUL.
+  ///
+  /// A null pointer results in an empty vector.
+  pub fn c_str_to_bytes(in_str: ctmbstr) -> Vec<u8> {
+    if in_str.is_null() {
+      return Vec::new();
+    }
     let c_str: &CStr = unsafe { CStr::from_ptr(in_str) };
-    let str_slice: &str = c_str.to_str().unwrap();
-    str_slice.to_owned()
+    c_str.to_bytes().to_vec()
   }
 
   pub fn bool_to_tidy_bool(bool_in: bool) -> Bool {
@@ -101,50 +142,218 @@ impl TidyUtil {
   }
 
   pub fn output_as_vector(tidy: &Tidy) -> Option<Vec<u8>> {
-    unsafe {
-      if !(*tidy.output).bp.is_null() {
-        let c_str: &CStr = CStr::from_ptr((*tidy.output).bp as *const i8);
-        return Some(c_str.to_bytes().to_vec());
-      }
+    if tidy.output.is_empty() {
+      None
+    } else {
+      Some(tidy.output.as_bytes().to_vec())
     }
-    None
   }
 
   pub fn errbuf_as_string(tidy: &Tidy) -> String {
-    unsafe { TidyUtil::c_str_to_owned((*tidy.errbuf).bp as *const i8) }
+    tidy.errbuf.to_string_lossy()
+  }
+}
+
+// Guards libtidy's process-wide language, which is written by set_language and read by libtidy whenever it creates a
+// document or reports a message.
+static LANGUAGE: RwLock<()> = RwLock::new(());
+
+pub(crate) fn read_language() -> RwLockReadGuard<'static, ()> {
+  LANGUAGE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn write_language() -> RwLockWriteGuard<'static, ()> {
+  LANGUAGE.write().unwrap_or_else(|e| e.into_inner())
+}
+
+// libtidy ignores the encoding and modifier of a language code and compares it case insensitively, with `-` and `_`
+// being equivalent.
+fn normalize_language(code: &str) -> String {
+  code
+    .split(['.', '@'])
+    .next()
+    .unwrap_or_default()
+    .to_ascii_lowercase()
+    .replace('-', "_")
+}
+
+// Create a buffer using the instance's allocator, so the memory limit covers it, or tidy's default allocator.
+fn new_buffer(allocator: *mut RustAllocator) -> Buffer {
+  if allocator.is_null() {
+    Buffer::new()
+  } else {
+    unsafe { Buffer::with_allocator(RustAllocator::as_tidy_allocator(allocator)) }
   }
 }
 
+fn current_language() -> String {
+  unsafe { TidyUtil::c_str_to_owned(tidyGetLanguage()) }
+}
+
+fn c_string(val: &str) -> Result<CString, TidyError> {
+  CString::new(val).map_err(|e| TidyError::Nul {
+    value: val.to_string(),
+    position: e.nul_position(),
+  })
+}
+
+/// State shared with the tidy callbacks, reachable from the TidyDoc through its app data.
+pub(crate) struct TidyContext {
+  diagnostics: RefCell<Vec<Diagnostic>>,
+  report_filter: RefCell<Option<ReportFilter>>,
+  config_callback: RefCell<Option<ConfigCallback>>,
+  config_change_callback: RefCell<Option<ConfigChangeCallback>>,
+  source_map: RefCell<Option<Vec<SourceMapping>>>,
+  poison: RefCell<Option<TidyError>>,
+  callback_panic: RefCell<Option<panic::Payload>>,
+}
+
+/// A tidy document together with its configuration, error and output buffers.
+///
+/// A Tidy exclusively owns its TidyDoc and all state tidy's callbacks reach through it, so it can be moved to another
+/// thread. It is not Sync, methods taking `&self` still modify the document and its buffers. Note that
+/// tidySetLanguage() changes the localization for the whole process. Use a [`TidyPool`] to share configured instances
+/// between worker threads.
+///
+/// When libtidy panics, e.g. because an allocation failed, creating an instance, parsing, repairing, diagnosing, saving
+/// and loading a config return TidyError::Panic instead of exiting the process. The instance is unusable afterwards. A
+/// panic in any other libtidy call, e.g. while setting an option, prints the message and aborts the process.
+///
+/// A panic in Rust code called by libtidy, i.e. a report filter, config callback or the reader and writer given to
+/// parse_reader and save_writer, doesn't unwind through libtidy. Further calls of the panicking code are skipped and
+/// the panic is resumed once the libtidy call returned.
 pub struct Tidy {
-  pub errbuf: *mut TidyBuffer,
-  pub output: *mut TidyBuffer,
+  errbuf: Buffer,
+  output: Buffer,
   tdoc: TidyDoc,
+  context: *mut TidyContext,
+  allocator: *mut RustAllocator,
+  parsed: bool,
 }
 
+// The TidyDoc, buffers, context and allocator are owned by the instance and never shared with another Tidy, the report filter is
+// required to be Send.
+unsafe impl Send for Tidy {}
+
 impl Tidy {
   pub fn new() -> Result<Tidy, TidyError> {
-    let errbuf: TidyBuffer = Default::default();
-    let b_errbuf = Box::from(errbuf);
-    let p_errbuf = Box::into_raw(b_errbuf);
+    Tidy::create(std::ptr::null_mut())
+  }
 
-    let output: TidyBuffer = Default::default();
-    let b_output = Box::from(output);
-    let p_output = Box::into_raw(b_output);
+  /// Create a Tidy instance whose document allocates its memory through given allocator.
+  ///
+  /// # Returns
+  /// The instance, or a TidyError if it couldn't be set up.
+  /// # Parameters
+  /// **allocator**	The allocator configuration, see [`Allocator`].
+  pub fn with_allocator(allocator: Allocator) -> Result<Tidy, TidyError> {
+    Tidy::create(Box::into_raw(Box::new(RustAllocator::new(allocator))))
+  }
 
-    let tdoc = unsafe { tidyCreate() };
+  fn create(p_allocator: *mut RustAllocator) -> Result<Tidy, TidyError> {
+    let context = TidyContext {
+      diagnostics: RefCell::new(Vec::new()),
+      report_filter: RefCell::new(None),
+      config_callback: RefCell::new(None),
+      config_change_callback: RefCell::new(None),
+      source_map: RefCell::new(None),
+      poison: RefCell::new(None),
+      callback_panic: RefCell::new(None),
+    };
+    let p_context = Box::into_raw(Box::from(context));
+
+    panic::install();
+    let tdoc = panic::guard(p_context, || unsafe {
+      if p_allocator.is_null() {
+        Ok(tidyCreate())
+      } else {
+        Ok(tidyCreateWithAllocator(RustAllocator::as_tidy_allocator(
+          p_allocator,
+        )))
+      }
+    });
+    let tdoc = match tdoc {
+      Ok(tdoc) => tdoc,
+      Err(e) => {
+        // The partially created document can't be released through tidy, its memory is freed directly.
+        unsafe {
+          if !p_allocator.is_null() {
+            (*p_allocator).release_all();
+            drop(Box::from_raw(p_allocator));
+          }
+          drop(Box::from_raw(p_context));
+        }
+        return Err(e);
+      }
+    };
 
     unsafe {
-      match tidySetErrorBuffer(tdoc, p_errbuf) {
-        0 => Ok(Tidy {
-          errbuf: p_errbuf,
-          output: p_output,
-          tdoc: tdoc,
-        }),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy set errorbuffer error"),
-        }),
-      }
+      tidySetAppData(tdoc, p_context as *mut c_void);
+      tidySetMessageCallback(tdoc, Some(diagnostic::message_callback));
+      tidySetConfigCallback(tdoc, Some(config::config_callback));
+      tidySetConfigChangeCallback(tdoc, Some(config::config_change_callback));
+      tidySetPrettyPrinterCallback(tdoc, Some(source_map::pretty_printer_callback));
+    }
+    let tidy = Tidy {
+      errbuf: new_buffer(p_allocator),
+      output: new_buffer(p_allocator),
+      tdoc: tdoc,
+      context: p_context,
+      allocator: p_allocator,
+      parsed: false,
+    };
+    match unsafe { tidySetErrorBuffer(tdoc, tidy.errbuf.as_ptr()) } {
+      0 => Ok(tidy),
+      rc => Err(TidyError::Create { status: rc }),
+    }
+  }
+
+  /// Get the memory accounting of an instance created with_allocator().
+  ///
+  /// # Returns
+  /// The memory usage of the document, or None if it uses tidy's default allocator.
+  pub fn memory_usage(&self) -> Option<MemoryUsage> {
+    self.allocator().map(RustAllocator::usage)
+  }
+
+  // Taken buffers may outlive the instance, so the contents of a buffer using its allocator are moved to one using
+  // tidy's default allocator.
+  fn hand_out(&self, buf: Buffer) -> Buffer {
+    if self.allocator.is_null() {
+      return buf;
+    }
+    let mut owned = Buffer::new();
+    owned.append(buf.as_bytes());
+    owned
+  }
+
+  fn allocator(&self) -> Option<&RustAllocator> {
+    if self.allocator.is_null() {
+      None
+    } else {
+      Some(unsafe { &*self.allocator })
+    }
+  }
+
+  fn context(&self) -> &TidyContext {
+    unsafe { &*self.context }
+  }
+
+  fn diagnostics_mark(&self) -> usize {
+    self.context().diagnostics.borrow().len()
+  }
+
+  fn diagnostics_since(&self, mark: usize) -> Vec<Diagnostic> {
+    let diagnostics = self.context().diagnostics.borrow();
+    diagnostics.get(mark..).unwrap_or_default().to_vec()
+  }
+
+  fn opt_name(&self, optid: TidyOptionId) -> Option<String> {
+    let opt = self.get_option(optid);
+    if opt.is_null() {
+      None
+    } else {
+      Some(Tidy::opt_get_name(opt))
     }
   }
 
@@ -208,6 +417,198 @@ impl Tidy {
     unsafe { tidyErrorCount(self.tdoc) }
   }
 
+  /// Get the error buffer tidy writes its report and dialogue messages to.
+  ///
+  /// # Returns
+  /// The messages written so far. The mutable borrow keeps further operations from writing to the buffer while the
+  /// contents are in use.
+  pub fn errbuf(&mut self) -> &Buffer {
+    &self.errbuf
+  }
+
+  /// Get the output buffer filled by save_buffer.
+  ///
+  /// # Returns
+  /// The document written by the last calls to save_buffer.
+  pub fn output(&self) -> &Buffer {
+    &self.output
+  }
+
+  /// Take the output buffer filled by save_buffer, leaving an empty one in its place.
+  ///
+  /// ```
+  /// # use tidy::*;
+  /// # fn main() -> Result<(), TidyError> {
+  /// let mut tidy = Tidy::new()?;
+  /// tidy.parse_bytes(b"<title>T</title><p>Hello")?;
+  /// tidy.save_buffer()?;
+  /// let output = tidy.take_output();
+  /// assert!(output.to_string_lossy().contains("<p>Hello</p>"));
+  /// assert!(tidy.output().is_empty());
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn take_output(&mut self) -> Buffer {
+    let output = std::mem::replace(&mut self.output, new_buffer(self.allocator));
+    self.hand_out(output)
+  }
+
+  /// Take the error buffer, leaving an empty one in its place for subsequent messages.
+  pub fn take_errbuf(&mut self) -> Result<Buffer, TidyError> {
+    let errbuf = new_buffer(self.allocator);
+    unsafe {
+      match tidySetErrorBuffer(self.tdoc, errbuf.as_ptr()) {
+        0 => {
+          let errbuf = std::mem::replace(&mut self.errbuf, errbuf);
+          Ok(self.hand_out(errbuf))
+        }
+        rc => Err(TidyError::Config {
+          path: None,
+          status: Some(rc),
+          diagnostics: Vec::new(),
+        }),
+      }
+    }
+  }
+
+  /// Get the messages tidy emitted so far, in the order they were reported.
+  ///
+  /// Messages accumulate over all operations on this instance until they are taken or cleared.
+  ///
+  /// # Returns
+  /// A copy of the collected diagnostics.
+  ///
+  /// ```
+  /// # use tidy::*;
+  /// # fn main() -> Result<(), TidyError> {
+  /// let mut tidy = Tidy::new()?;
+  /// tidy.parse_string(b"<title>T</title><p><b>bold</p>".to_vec())?;
+  ///
+  /// let warnings: Vec<_> = tidy
+  ///   .diagnostics()
+  ///   .into_iter()
+  ///   .filter(|d| d.level == TidyReportLevel::TidyWarning)
+  ///   .collect();
+  /// assert!(warnings.iter().any(|d| d.key == "MISSING_ENDTAG_BEFORE" && d.line == 1));
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn diagnostics(&self) -> Vec<Diagnostic> {
+    self.context().diagnostics.borrow().clone()
+  }
+
+  /// Take the messages tidy emitted so far, leaving the collection empty.
+  ///
+  /// # Returns
+  /// The collected diagnostics.
+  pub fn take_diagnostics(&self) -> Vec<Diagnostic> {
+    self.context().diagnostics.take()
+  }
+
+  /// Discard the messages tidy emitted so far.
+  pub fn clear_diagnostics(&self) {
+    self.context().diagnostics.borrow_mut().clear();
+  }
+
+  /// Register a closure that decides per message whether it is emitted.
+  ///
+  /// The closure sees every message before it is written to the error buffer. Messages it rejects by returning false
+  /// are neither written nor collected as diagnostics. Registering a new filter replaces the previous one. The closure
+  /// must be Send, as it moves along with the instance.
+  ///
+  /// # Parameters
+  /// **filter**	The closure to call for each message.
+  ///
+  /// ```
+  /// # use tidy::*;
+  /// # fn main() -> Result<(), TidyError> {
+  /// let mut tidy = Tidy::new()?;
+  /// tidy.set_report_filter(|d| d.key != "PROPRIETARY_ATTRIBUTE");
+  /// tidy.parse_string(b"<title>T</title><p foo='bar'>x</p>".to_vec())?;
+  ///
+  /// assert!(tidy.diagnostics().iter().all(|d| d.key != "PROPRIETARY_ATTRIBUTE"));
+  ///
+  /// tidy.set_report_filter(|_| panic!("rejected"));
+  /// let parse = std::panic::AssertUnwindSafe(|| tidy.parse_bytes(b"<p foo='bar'>x"));
+  /// assert!(std::panic::catch_unwind(parse).is_err());
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn set_report_filter<F>(&self, filter: F)
+  where
+    F: FnMut(&Diagnostic) -> bool + Send + 'static,
+  {
+    *self.context().report_filter.borrow_mut() = Some(Box::new(filter));
+  }
+
+  /// Remove the closure registered with set_report_filter(), so all messages are emitted again.
+  pub fn clear_report_filter(&self) {
+    *self.context().report_filter.borrow_mut() = None;
+  }
+
+  /// Register a closure handling options tidy doesn't know.
+  ///
+  /// The closure receives the name and value of every unknown option found by load_config() or opt_parse_value(). It
+  /// returns true if it handled the option, false to let tidy report it as an unknown configuration option.
+  /// Registering a new callback replaces the previous one.
+  ///
+  /// # Parameters
+  /// **callback**	The closure to call for each unknown option.
+  ///
+  /// ```
+  /// # use tidy::*;
+  /// # use std::sync::{Arc, Mutex};
+  /// # fn main() -> Result<(), TidyError> {
+  /// let extensions = Arc::new(Mutex::new(Vec::new()));
+  /// let tidy = Tidy::new()?;
+  /// let seen = Arc::clone(&extensions);
+  /// tidy.set_config_callback(move |name, value| {
+  ///   if name.starts_with("x-") {
+  ///     seen.lock().unwrap().push((name.to_string(), value.to_string()));
+  ///     true
+  ///   } else {
+  ///     false
+  ///   }
+  /// });
+  ///
+  /// tidy.opt_parse_value("x-site", "intranet")?;
+  /// assert!(tidy.opt_parse_value("indnet", "auto").is_err());
+  /// assert_eq!(*extensions.lock().unwrap(), vec![("x-site".to_string(), "intranet".to_string())]);
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn set_config_callback<F>(&self, callback: F)
+  where
+    F: FnMut(&str, &str) -> bool + Send + 'static,
+  {
+    *self.context().config_callback.borrow_mut() = Some(Box::new(callback));
+  }
+
+  /// Remove the closure registered with set_config_callback(), so all unknown options are reported again.
+  pub fn clear_config_callback(&self) {
+    *self.context().config_callback.borrow_mut() = None;
+  }
+
+  /// Register a closure notified whenever an option is about to change.
+  ///
+  /// The closure receives the id and name of the option. Temporary changes tidy makes while processing a document are
+  /// not reported. Registering a new callback replaces the previous one. Note that libtidy only implements this
+  /// notification as of 5.7, earlier versions never call the closure.
+  ///
+  /// # Parameters
+  /// **callback**	The closure to call for each change.
+  pub fn set_config_change_callback<F>(&self, callback: F)
+  where
+    F: FnMut(TidyOptionId, &str) + Send + 'static,
+  {
+    *self.context().config_change_callback.borrow_mut() = Some(Box::new(callback));
+  }
+
+  /// Remove the closure registered with set_config_change_callback().
+  pub fn clear_config_change_callback(&self) {
+    *self.context().config_change_callback.borrow_mut() = None;
+  }
+
   /// Get the version number for the current library.
   /// Returns
   /// The string representing the version number.
@@ -215,6 +616,87 @@ impl Tidy {
     unsafe { TidyUtil::c_str_to_owned(tidyLibraryVersion()) }
   }
 
+  /// Set the language of tidy's messages.
+  ///
+  /// The language applies to the whole process, not just this instance, diagnostics carry the English text in addition,
+  /// see [`Diagnostic::default_message`]. The change waits for all instances to finish creating, parsing, repairing,
+  /// diagnosing, saving and loading a config, which hold a shared lock on the language while they run.
+  /// It must not be called from a callback, e.g. a report filter, of such an operation, which would deadlock.
+  ///
+  /// # Returns
+  /// TidySeverity::Success if the language was applied, TidySeverity::Warning if tidy fell back to a related language,
+  /// e.g. `es` for `es_ar`, or a TidyError if the language isn't installed.
+  /// # Parameters
+  /// **code**	A POSIX or Windows language code, e.g. `de` or `pt_br`.
+  ///
+  /// ```
+  /// # use tidy::*;
+  /// # fn main() -> Result<(), TidyError> {
+  /// assert!(Tidy::available_languages().iter().any(|l| l == "en"));
+  /// assert!(Tidy::set_language("xx").is_err());
+  /// Tidy::set_language("en")?;
+  /// assert_eq!(Tidy::language(), "en");
+  /// assert_eq!(Tidy::set_language("EN")?, TidySeverity::Success);
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn set_language(code: &str) -> Result<TidySeverity, TidyError> {
+    let c_code = c_string(code)?;
+    let _lock = write_language();
+    unsafe {
+      match tidySetLanguage(c_code.as_ptr()) {
+        Bool_yes if current_language() == normalize_language(code) => Ok(TidySeverity::Success),
+        Bool_yes => Ok(TidySeverity::Warning),
+        _ => Err(TidyError::Language {
+          code: code.to_string(),
+        }),
+      }
+    }
+  }
+
+  /// Get the language of tidy's messages.
+  ///
+  /// # Returns
+  /// The code of the current language, e.g. `en`.
+  pub fn language() -> String {
+    let _lock = read_language();
+    current_language()
+  }
+
+  /// List the languages tidy's messages are available in.
+  ///
+  /// # Returns
+  /// The codes of the installed languages.
+  pub fn available_languages() -> Vec<String> {
+    let mut languages = Vec::new();
+    unsafe {
+      let mut iter = getInstalledLanguageList();
+      while !iter.is_null() {
+        languages.push(TidyUtil::c_str_to_owned(getNextInstalledLanguage(
+          &mut iter,
+        )));
+      }
+    }
+    languages
+  }
+
+  /// Get one of tidy's built-in strings in the current language.
+  ///
+  /// # Returns
+  /// The string for the message code, e.g. Diagnostic::code, empty if there is none.
+  pub fn localized_string(code: c_uint) -> String {
+    let _lock = read_language();
+    unsafe { TidyUtil::c_str_to_owned(tidyLocalizedString(code)) }
+  }
+
+  /// Get one of tidy's built-in strings in English.
+  ///
+  /// # Returns
+  /// The string for the message code, empty if there is none.
+  pub fn default_string(code: c_uint) -> String {
+    unsafe { TidyUtil::c_str_to_owned(tidyDefaultString(code)) }
+  }
+
   /// Load an ASCII Tidy configuration file and set the configuration per its contents.
   ///
   /// # Returns
@@ -223,16 +705,18 @@ impl Tidy {
   /// # Parameters
   /// **config_file**	The complete path to the file to load.
   pub fn load_config(&self, config_file: &str) -> Result<TidySeverity, TidyError> {
-    unsafe {
-      let c_config_file = CString::new(config_file).unwrap();
+    panic::guard(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
+      let c_config_file = c_string(config_file)?;
       match tidyLoadConfig(self.tdoc, c_config_file.as_ptr()) {
         0 => Ok(TidySeverity::Success),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy load config error"),
+        rc => Err(TidyError::Config {
+          path: Some(config_file.to_string()),
+          status: Some(rc),
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 
   /// Get the release date for the current library.
@@ -255,16 +739,18 @@ impl Tidy {
   /// Returns TidySeverity::Success upon success, or a TidyError if there was an error.
 
   pub fn set_char_encoding(&self, encnam: &str) -> Result<TidySeverity, TidyError> {
-    unsafe {
-      let c_encnam = CString::new(encnam).unwrap();
+    panic::resuming(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
+      let c_encnam = c_string(encnam)?;
       match tidySetCharEncoding(self.tdoc, c_encnam.as_ptr()) {
         0 => Ok(TidySeverity::Success),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy set char encoding error"),
+        rc => Err(TidyError::Encoding {
+          name: encnam.to_string(),
+          status: rc,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 
   /// Set the input encoding for parsing markup.
@@ -276,18 +762,20 @@ impl Tidy {
   /// # Returns
   /// Returns TidySeverity::Success upon success, or a TidyError if there was an error.
   pub fn set_in_char_encoding(&self, encnam: &str) -> Result<TidySeverity, TidyError> {
-    unsafe {
-      let c_encnam = CString::new(encnam).unwrap();
+    panic::resuming(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
+      let c_encnam = c_string(encnam)?;
       match tidySetInCharEncoding(self.tdoc, c_encnam.as_ptr()) {
         0 => Ok(TidySeverity::Success),
         1 => Ok(TidySeverity::Warning),
         2 => Ok(TidySeverity::Error),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy set in char encoding error"),
+        rc => Err(TidyError::Encoding {
+          name: encnam.to_string(),
+          status: rc,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 
   /// Set the output encoding for writing markup.
@@ -299,16 +787,18 @@ impl Tidy {
   /// # Returns
   /// Returns TidySeverity::Success upon success, or a TidyError if there was an error.
   pub fn set_out_char_encoding(&self, encnam: &str) -> Result<TidySeverity, TidyError> {
-    unsafe {
-      let c_encnam = CString::new(encnam).unwrap();
+    panic::resuming(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
+      let c_encnam = c_string(encnam)?;
       match tidySetOutCharEncoding(self.tdoc, c_encnam.as_ptr()) {
         0 => Ok(TidySeverity::Success),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy set out char encoding error"),
+        rc => Err(TidyError::Encoding {
+          name: encnam.to_string(),
+          status: rc,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 
   /// Get status of current document.
@@ -350,10 +840,20 @@ impl Tidy {
   /// # Parameters
   /// **optnam**	The name of the Tidy configuration option.
   /// # Returns
-  /// The TidyOption of the given optname.
-  pub fn get_option_by_name(&self, optnam: &str) -> TidyOption {
-    let c_optnam = CString::new(optnam).unwrap();
-    unsafe { tidyGetOptionByName(self.tdoc, c_optnam.as_ptr()) }
+  /// The TidyOption of the given optname, or a TidyError if there is no such option.
+  pub fn get_option_by_name(&self, optnam: &str) -> Result<TidyOption, TidyError> {
+    let c_optnam = c_string(optnam)?;
+    let opt = unsafe { tidyGetOptionByName(self.tdoc, c_optnam.as_ptr()) };
+    if opt.is_null() {
+      Err(TidyError::Option {
+        id: None,
+        name: Some(optnam.to_string()),
+        value: None,
+        diagnostics: Vec::new(),
+      })
+    } else {
+      Ok(opt)
+    }
   }
 
   ///   Copy current configuration settings from one document to another.
@@ -366,11 +866,13 @@ impl Tidy {
   ///   Returns TidySeverity::Success indicating success or a TidyError on failure.
   pub fn opt_copy_config(&self, tdoc_to: TidyDoc) -> Result<TidySeverity, TidyError> {
     unsafe {
+      let mark = self.diagnostics_mark();
       match tidyOptCopyConfig(tdoc_to, self.tdoc) {
         Bool_yes => Ok(TidySeverity::Success),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy reset to default error"),
+        _ => Err(TidyError::Config {
+          path: None,
+          status: None,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
     }
@@ -509,8 +1011,10 @@ impl Tidy {
   /// # Returns
   /// The TidyOptionId of the given optname.
   pub fn opt_get_id_for_name(optnam: &str) -> TidyOptionId {
-    let c_optnam = CString::new(optnam).unwrap();
-    unsafe { tidyOptGetIdForName(c_optnam.as_ptr()) }
+    match CString::new(optnam) {
+      Ok(c_optnam) => unsafe { tidyOptGetIdForName(c_optnam.as_ptr()) },
+      Err(_) => TidyOptionId::TidyUnknownOption,
+    }
   }
 
   /// Get current option value as an integer.
@@ -553,17 +1057,20 @@ impl Tidy {
   /// Returns TidySeverity::Success indicating success or a TidyError on failure.
   /// **val**	The value to set.
   pub fn opt_parse_value(&self, optnam: &str, val: &str) -> Result<TidySeverity, TidyError> {
-    unsafe {
-      let c_optnam = CString::new(optnam).unwrap();
-      let c_val = CString::new(val).unwrap();
+    panic::resuming(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
+      let c_optnam = c_string(optnam)?;
+      let c_val = c_string(val)?;
       match tidyOptParseValue(self.tdoc, c_optnam.as_ptr(), c_val.as_ptr()) {
         Bool_yes => Ok(TidySeverity::Success),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy option parse value error"),
+        _ => Err(TidyError::Option {
+          id: None,
+          name: Some(optnam.to_string()),
+          value: Some(val.to_string()),
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 
   /// Reset all options to their default values.
@@ -571,15 +1078,18 @@ impl Tidy {
   /// # Returns
   /// Returns TidySeverity::Success indicating success or TidyError on failure.
   pub fn opt_reset_all_to_default(&self) -> Result<TidySeverity, TidyError> {
-    unsafe {
+    panic::resuming(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
       match tidyOptResetAllToDefault(self.tdoc) {
         Bool_yes => Ok(TidySeverity::Success),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy reset to default error"),
+        _ => Err(TidyError::Option {
+          id: None,
+          name: None,
+          value: None,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 
   /// Reset option to default value by ID.
@@ -589,15 +1099,18 @@ impl Tidy {
   /// # Returns
   /// Returns a TidySeverity::Success indicating success or TidyError on failure.
   pub fn opt_reset_to_default(&self, optid: TidyOptionId) -> Result<TidySeverity, TidyError> {
-    unsafe {
+    panic::resuming(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
       match tidyOptResetToDefault(self.tdoc, optid) {
         Bool_yes => Ok(TidySeverity::Success),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy reset to default error"),
+        _ => Err(TidyError::Option {
+          id: Some(optid),
+          name: self.opt_name(optid),
+          value: None,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 
   /// Apply a snapshot of config settings to a document.
@@ -605,15 +1118,17 @@ impl Tidy {
   /// # Returns
   /// Returns a TidySeverity::Success indicating success or TidyError on failure.
   pub fn opt_reset_to_snapshot(&self) -> Result<TidySeverity, TidyError> {
-    unsafe {
+    panic::resuming(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
       match tidyOptResetToSnapshot(self.tdoc) {
         Bool_yes => Ok(TidySeverity::Success),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy reset to snapshot error"),
+        _ => Err(TidyError::Config {
+          path: None,
+          status: None,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 
   /// Set option value as a Boolean flag.
@@ -624,15 +1139,18 @@ impl Tidy {
   /// # Returns
   /// Returns a TidySeverity::Success indicating success or TidyError on failure.
   pub fn opt_set_bool(&self, optid: TidyOptionId, val: bool) -> Result<TidySeverity, TidyError> {
-    unsafe {
+    panic::resuming(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
       match tidyOptSetBool(self.tdoc, optid, TidyUtil::bool_to_tidy_bool(val)) {
         Bool_yes => Ok(TidySeverity::Success),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy  option set bool error"),
+        _ => Err(TidyError::Option {
+          id: Some(optid),
+          name: self.opt_name(optid),
+          value: Some(val.to_opt_value()),
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 
   /// Set option value as an integer.
@@ -643,15 +1161,18 @@ impl Tidy {
   /// # Returns
   /// Returns a TidySeverity::Success indicating success or TidyError on failure.
   pub fn opt_set_int(&self, optid: TidyOptionId, val: c_ulong) -> Result<TidySeverity, TidyError> {
-    unsafe {
+    panic::resuming(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
       match tidyOptSetInt(self.tdoc, optid, val) {
         Bool_yes => Ok(TidySeverity::Success),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy option set int error"),
+        _ => Err(TidyError::Option {
+          id: Some(optid),
+          name: self.opt_name(optid),
+          value: Some(val.to_string()),
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 
   /// Set the option value as a string.
@@ -662,16 +1183,117 @@ impl Tidy {
   /// # Returns
   /// Returns a TidySeverity::Success indicating success or TidyError on failure.
   pub fn opt_set_value(&self, optid: TidyOptionId, val: &str) -> Result<TidySeverity, TidyError> {
-    unsafe {
-      let c_val = CString::new(val).unwrap();
+    panic::resuming(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
+      let c_val = c_string(val)?;
       match tidyOptSetValue(self.tdoc, optid, c_val.as_ptr()) {
         Bool_yes => Ok(TidySeverity::Success),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy option set value error"),
+        _ => Err(TidyError::Option {
+          id: Some(optid),
+          name: self.opt_name(optid),
+          value: Some(val.to_string()),
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
+    })
+  }
+
+  /// Describe every option tidy knows about.
+  ///
+  /// # Returns
+  /// Name, id, category, type, default and current value, pick list, description and related options of each option,
+  /// in the order tidy lists them.
+  ///
+  /// ```
+  /// # use tidy::*;
+  /// # fn main() -> Result<(), TidyError> {
+  /// let tidy = Tidy::new()?;
+  /// let options = tidy.options();
+  /// let indent = options.iter().find(|o| o.name == "indent").unwrap();
+  /// assert_eq!(indent.id, TidyOptionId::TidyIndentContent);
+  /// assert!(indent.picks.iter().any(|p| p == "auto"));
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn options(&self) -> Vec<OptionInfo> {
+    let mut options = Vec::new();
+    unsafe {
+      let mut iter = tidyGetOptionList(self.tdoc);
+      while !iter.is_null() {
+        let opt = tidyGetNextOption(self.tdoc, &mut iter);
+        if !opt.is_null() {
+          options.push(OptionInfo::new(self, opt));
+        }
+      }
+    }
+    options
+  }
+
+  /// Take a snapshot of all option values of this document.
+  ///
+  /// Read-only options and string options without a value are left out, so applying the snapshot doesn't clear such an
+  /// option where it has a value, see [`Tidy::apply_config`].
+  ///
+  /// # Returns
+  /// A TidyConfig with an entry per option.
+  pub fn config(&self) -> TidyConfig {
+    let mut config = TidyConfig::new();
+    unsafe {
+      let mut iter = tidyGetOptionList(self.tdoc);
+      while !iter.is_null() {
+        let opt = tidyGetNextOption(self.tdoc, &mut iter);
+        if opt.is_null() || TidyUtil::tidy_bool_to_bool(tidyOptIsReadOnly(opt)) {
+          continue;
+        }
+        if let Some(val) = config::opt_value_string(self, opt) {
+          config.insert(Tidy::opt_get_name(opt), val);
+        }
+      }
     }
+    config
+  }
+
+  /// Apply the option values of a snapshot to this document.
+  ///
+  /// Options that are not part of the snapshot keep their current value, and the values of list options like
+  /// new-blocklevel-tags are added to the current list rather than replacing it. Applying a snapshot taken with
+  /// config() is therefore only a faithful round trip on an instance with default options; call
+  /// opt_reset_all_to_default() first to get an exact copy of the snapshot.
+  ///
+  /// # Parameters
+  /// **config**	The snapshot to apply.
+  /// # Returns
+  /// Returns TidySeverity::Success indicating success or a TidyError for the first value tidy rejects.
+  pub fn apply_config(&self, config: &TidyConfig) -> Result<TidySeverity, TidyError> {
+    for (optnam, val) in config {
+      self.opt_parse_value(optnam, val)?;
+    }
+    Ok(TidySeverity::Success)
+  }
+
+  /// Set an option value, with the value type checked at compile time.
+  ///
+  /// See the [`opt`] module for the available options and their value types.
+  ///
+  /// # Parameters
+  /// **opt**	The option to set, e.g. opt::IndentSpaces.
+  /// **val**	The value to set.
+  /// # Returns
+  /// Returns a TidySeverity::Success indicating success or TidyError on failure.
+  pub fn opt_set<O: TidyOpt>(&self, _opt: O, val: O::Value) -> Result<TidySeverity, TidyError> {
+    self.opt_set_value(O::ID, &val.to_opt_value())
+  }
+
+  /// Get an option value, with the value type checked at compile time.
+  ///
+  /// See the [`opt`] module for the available options and their value types.
+  ///
+  /// # Parameters
+  /// **opt**	The option to get, e.g. opt::IndentSpaces.
+  /// # Returns
+  /// The current value of the option.
+  pub fn opt_get<O: TidyOpt>(&self, _opt: O) -> O::Value {
+    O::Value::from_tidy(self, O::ID)
   }
 
   ///  Take a snapshot of current config settings.
@@ -682,34 +1304,102 @@ impl Tidy {
   /// Returns a TidySeverity::Success indicating success or TidyError on failure.
   pub fn opt_snapshot(&self) -> Result<TidySeverity, TidyError> {
     unsafe {
+      let mark = self.diagnostics_mark();
       match tidyOptSnapshot(self.tdoc) {
         Bool_yes => Ok(TidySeverity::Success),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy option snapshot error"),
+        _ => Err(TidyError::Config {
+          path: None,
+          status: None,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
     }
   }
 
+  /// Prepare the instance for the next document, keeping its configuration.
+  ///
+  /// Options are restored to the values they had when the last parse started, which undoes changes made while
+  /// processing the previous document, e.g. setting TidyForceOutput after diagnostics. If nothing was parsed since the
+  /// instance was created or last reset, the current options are kept. The error and output buffers and the collected
+  /// diagnostics are cleared, the report filter is kept. The previous document is only released by the next parse:
+  /// until then its tree remains reachable through root() and body(), and status(), error_count() and
+  /// warning_count() still report its results. Instances handed out by a [`TidyPool`] behave the same.
+  ///
+  /// Reusing an instance avoids creating a new tidy document and applying all options again for every input.
+  ///
+  /// # Returns
+  /// Returns a TidySeverity::Success indicating success or TidyError on failure.
+  ///
+  /// ```
+  /// # use tidy::*;
+  /// # fn main() -> Result<(), TidyError> {
+  /// let mut tidy = Tidy::new()?;
+  /// tidy.opt_set_bool(TidyOptionId::TidyXmlTags, true)?;
+  /// for doc in &["<a>1</a>", "<b>2</b>"] {
+  ///   tidy.parse_bytes(doc.as_bytes())?;
+  ///   tidy.opt_set_bool(TidyOptionId::TidyForceOutput, true)?;
+  ///   tidy.save_buffer()?;
+  ///   assert!(tidy.output().to_string_lossy().contains(doc));
+  ///   tidy.reset()?;
+  ///   assert!(!tidy.opt_get(opt::ForceOutput));
+  ///   assert!(tidy.output().is_empty());
+  /// }
+  ///
+  /// let mut tidy = Tidy::new()?;
+  /// tidy.opt_set_bool(TidyOptionId::TidyXmlTags, true)?;
+  /// tidy.reset()?;
+  /// assert!(tidy.opt_get(opt::XmlTags));
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn reset(&mut self) -> Result<TidySeverity, TidyError> {
+    self.errbuf.clear();
+    self.output.clear();
+    self.clear_diagnostics();
+    let parsed = std::mem::replace(&mut self.parsed, false);
+    panic::resuming(self.context, || unsafe {
+      let rc = if parsed {
+        tidyOptResetToSnapshot(self.tdoc)
+      } else {
+        tidyOptSnapshot(self.tdoc)
+      };
+      match rc {
+        Bool_yes => Ok(TidySeverity::Success),
+        _ => Err(TidyError::Config {
+          path: None,
+          status: None,
+          diagnostics: self.diagnostics(),
+        }),
+      }
+    })
+  }
+
+  // Parsing snapshots the options as well, but not if it fails before reading the input, so the snapshot is taken here
+  // to make reset() restore the options of the current document.
+  fn snapshot_options(&mut self) {
+    unsafe { tidyOptSnapshot(self.tdoc) };
+    self.parsed = true;
+  }
+
   // Diagnose and repair
 
   /// Execute configured cleanup and repair operations on parsed markup.
   ///
   /// # Returns
   /// An integer representing the status.
-  pub fn clean_and_repair(&self) -> Result<TidySeverity, TidyError> {
-    unsafe {
+  pub fn clean_and_repair(&mut self) -> Result<TidySeverity, TidyError> {
+    panic::guard(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
       match tidyCleanAndRepair(self.tdoc) {
         0 => Ok(TidySeverity::Success),
         1 => Ok(TidySeverity::Warning),
         2 => Ok(TidySeverity::Error),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy clean and repair error"),
+        rc => Err(TidyError::Repair {
+          status: rc,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 
   /// Reports the document type into the output sink.
@@ -734,17 +1424,18 @@ impl Tidy {
   /// # Returns
   /// An integer representing the status.
   pub fn run_diagnostics(&self) -> Result<TidySeverity, TidyError> {
-    unsafe {
+    panic::guard(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
       match tidyRunDiagnostics(self.tdoc) {
         0 => Ok(TidySeverity::Success),
         1 => Ok(TidySeverity::Warning),
         2 => Ok(TidySeverity::Error),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy run diagnostics error"),
+        rc => Err(TidyError::Diagnose {
+          status: rc,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 
   // Document Parse
@@ -754,37 +1445,97 @@ impl Tidy {
   /// Returns tTidySeverity::Error indicating that errors were present in the document, TidySeverity::Warning indicating warnings, and TidySeverity::Success in the case of everything being okay.
   /// # Parameters  
   /// **filename**	The filename to parse.
-  pub fn parse_file(&self, filename: &str) -> Result<TidySeverity, TidyError> {
-    unsafe {
-      let c_filename = CString::new(filename).unwrap();
+  pub fn parse_file(&mut self, filename: &str) -> Result<TidySeverity, TidyError> {
+    self.snapshot_options();
+    panic::guard(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
+      let c_filename = c_string(filename)?;
       match tidyParseFile(self.tdoc, c_filename.as_ptr()) {
         0 => Ok(TidySeverity::Success),
         1 => Ok(TidySeverity::Warning),
         2 => Ok(TidySeverity::Error),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy parse file error"),
+        rc if rc == -ENOENT => Err(TidyError::io(filename, rc)),
+        rc => Err(TidyError::Parse {
+          path: Some(filename.to_string()),
+          status: rc,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 
   /// Parse markup from the standard input.
   ///
   /// Returns
   /// Returns tTidySeverity::Error indicating that errors were present in the document, TidySeverity::Warning indicating warnings, and TidySeverity::Success in the case of everything being okay.
-  pub fn parse_stdin(&self) -> Result<TidySeverity, TidyError> {
-    unsafe {
+  pub fn parse_stdin(&mut self) -> Result<TidySeverity, TidyError> {
+    self.snapshot_options();
+    panic::guard(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
       match tidyParseStdin(self.tdoc) {
         0 => Ok(TidySeverity::Success),
         1 => Ok(TidySeverity::Warning),
         2 => Ok(TidySeverity::Error),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy parse stdin error"),
+        rc => Err(TidyError::Parse {
+          path: None,
+          status: rc,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
+  }
+
+  /// Parse markup from a reader.
+  ///
+  /// The input is pulled from the reader in chunks while tidy parses, so it doesn't have to be buffered in full first.
+  ///
+  /// # Returns
+  /// Returns TidySeverity::Error indicating that errors were present in the document, TidySeverity::Warning indicating warnings, and TidySeverity::Success in the case of everything being okay.
+  /// A read error ends the input and is returned as TidyError::Io.
+  /// # Parameters
+  /// **reader**	The reader to parse.
+  ///
+  /// ```
+  /// # use tidy::*;
+  /// # fn main() -> Result<(), TidyError> {
+  /// let input: &[u8] = b"<title>T</title><p>From a reader";
+  /// let mut tidy = Tidy::new()?;
+  /// tidy.parse_reader(input)?;
+  /// assert!(tidy.body().and_then(|body| body.first_child()).is_some());
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn parse_reader<R: Read>(&mut self, reader: R) -> Result<TidySeverity, TidyError> {
+    self.snapshot_options();
+    panic::guard(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
+      let mut reader = ReaderSource::new(reader);
+      let mut source: TidyInputSource = std::mem::zeroed();
+      let rc = if reader.init(&mut source) {
+        tidyParseSource(self.tdoc, &mut source)
+      } else {
+        -EINVAL
+      };
+      if let Some(payload) = reader.take_panic() {
+        std::panic::resume_unwind(payload);
+      }
+      if let Some(error) = reader.take_error() {
+        return Err(TidyError::Io {
+          path: None,
+          error: Arc::new(error),
+        });
+      }
+      match rc {
+        0 => Ok(TidySeverity::Success),
+        1 => Ok(TidySeverity::Warning),
+        2 => Ok(TidySeverity::Error),
+        rc => Err(TidyError::Parse {
+          path: None,
+          status: rc,
+          diagnostics: self.diagnostics_since(mark),
+        }),
+      }
+    })
   }
 
   /// Parse markup in given string.
@@ -793,22 +1544,184 @@ impl Tidy {
   /// Returns tTidySeverity::Error indicating that errors were present in the document, TidySeverity::Warning indicating warnings, and TidySeverity::Success in the case of everything being okay.
   /// # Parameters  
   /// **content**	The string to parse.
-  pub fn parse_string(&self, content: Vec<u8>) -> Result<TidySeverity, TidyError> {
-    unsafe {
-      let c_input = CString::from_vec_unchecked(content);
-      let raw_input = c_input.into_raw();
-      let rc = tidyParseString(self.tdoc, raw_input);
-      let _c_input = CString::from_raw(raw_input);
+  pub fn parse_string(&mut self, content: Vec<u8>) -> Result<TidySeverity, TidyError> {
+    self.parse_bytes(&content)
+  }
+
+  /// Parse markup in given byte slice.
+  ///
+  /// The input is read in full, embedded NUL bytes don't terminate it, so multibyte encodings like UTF-16 can be parsed
+  /// when the input encoding is set accordingly. The slice is handed to tidy without copying.
+  ///
+  /// # Returns
+  /// Returns TidySeverity::Error indicating that errors were present in the document, TidySeverity::Warning indicating warnings, and TidySeverity::Success in the case of everything being okay.
+  /// # Parameters
+  /// **content**	The bytes to parse.
+  ///
+  /// ```
+  /// # use tidy::*;
+  /// # fn main() -> Result<(), TidyError> {
+  /// let mut tidy = Tidy::new()?;
+  /// tidy.parse_bytes(b"<title>T</title><p>one\0<b>two</b></p>")?;
+  /// let p = tidy.body().and_then(|body| body.first_child()).unwrap();
+  /// assert!(p.children().any(|n| n.name().as_deref() == Some("b")));
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn parse_bytes(&mut self, content: &[u8]) -> Result<TidySeverity, TidyError> {
+    self.snapshot_options();
+    if content.len() > c_uint::MAX as usize {
+      return Err(TidyError::Io {
+        path: None,
+        error: Arc::new(io::Error::new(
+          io::ErrorKind::InvalidInput,
+          "input exceeds the maximum tidy buffer size",
+        )),
+      });
+    }
+    panic::guard(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
+      let mut buf: TidyBuffer = std::mem::zeroed();
+      // tidy only reads from an attached input buffer, it never writes to or frees it.
+      tidyBufAttach(
+        &mut buf,
+        content.as_ptr() as *mut byte,
+        content.len() as c_uint,
+      );
+      let rc = tidyParseBuffer(self.tdoc, &mut buf);
+      tidyBufDetach(&mut buf);
       match rc {
         0 => Ok(TidySeverity::Success),
         1 => Ok(TidySeverity::Warning),
         2 => Ok(TidySeverity::Error),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy parse string error"),
+        rc => Err(TidyError::Parse {
+          path: None,
+          status: rc,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
+  }
+
+  // Document tree
+  /// Get the root node of the document tree.
+  ///
+  /// # Returns
+  /// The root node, which is the parent of the doctype, the html element and any top level comments.
+  pub fn root(&self) -> Node<'_> {
+    Node::from_raw(self, unsafe { tidyGetRoot(self.tdoc) }).unwrap()
+  }
+
+  /// Get the html element of the document tree.
+  ///
+  /// # Returns
+  /// The html node, or None if the document has not been parsed yet.
+  pub fn html(&self) -> Option<Node<'_>> {
+    Node::from_raw(self, unsafe { tidyGetHtml(self.tdoc) })
+  }
+
+  /// Get the head element of the document tree.
+  ///
+  /// # Returns
+  /// The head node, or None if the document doesn't have one.
+  pub fn head(&self) -> Option<Node<'_>> {
+    Node::from_raw(self, unsafe { tidyGetHead(self.tdoc) })
+  }
+
+  /// Get the body element of the document tree.
+  ///
+  /// # Returns
+  /// The body node, or None if the document doesn't have one.
+  pub fn body(&self) -> Option<Node<'_>> {
+    Node::from_raw(self, unsafe { tidyGetBody(self.tdoc) })
+  }
+
+  /// Get a mutable handle to the root node of the document tree, see [`NodeMut`].
+  pub fn root_mut(&mut self) -> NodeMut<'_> {
+    let tnod = unsafe { tidyGetRoot(self.tdoc) };
+    NodeMut::from_raw(self, tnod).unwrap()
+  }
+
+  /// Get a mutable handle to the html element of the document tree.
+  ///
+  /// # Returns
+  /// The html node, or None if the document has not been parsed yet.
+  pub fn html_mut(&mut self) -> Option<NodeMut<'_>> {
+    let tnod = unsafe { tidyGetHtml(self.tdoc) };
+    NodeMut::from_raw(self, tnod)
+  }
+
+  /// Get a mutable handle to the head element of the document tree.
+  ///
+  /// # Returns
+  /// The head node, or None if the document doesn't have one.
+  pub fn head_mut(&mut self) -> Option<NodeMut<'_>> {
+    let tnod = unsafe { tidyGetHead(self.tdoc) };
+    NodeMut::from_raw(self, tnod)
+  }
+
+  /// Get a mutable handle to the body element of the document tree.
+  ///
+  /// # Returns
+  /// The body node, or None if the document doesn't have one.
+  pub fn body_mut(&mut self) -> Option<NodeMut<'_>> {
+    let tnod = unsafe { tidyGetBody(self.tdoc) };
+    NodeMut::from_raw(self, tnod)
+  }
+
+  /// Discard all nodes matching a predicate, together with their children.
+  ///
+  /// The tree is walked in document order, the children of a discarded node aren't visited.
+  ///
+  /// ```
+  /// # use tidy::*;
+  /// # fn main() -> Result<(), TidyError> {
+  /// let mut tidy = Tidy::new()?;
+  /// tidy.parse_string(b"<title>T</title><script>a()</script><p>Text<script>b()</script>".to_vec())?;
+  /// tidy.clean_and_repair()?;
+  ///
+  /// let discarded = tidy.discard_nodes(|node| node.tag_id() == TidyTagId::TidyTag_SCRIPT);
+  /// assert_eq!(discarded, 2);
+  /// tidy.save_buffer()?;
+  /// assert!(!tidy.output().to_string_lossy().contains("<script"));
+  /// # Ok(())
+  /// # }
+  /// ```
+  ///
+  /// # Returns
+  /// The number of nodes discarded, not counting their children.
+  pub fn discard_nodes<F>(&mut self, mut discard: F) -> usize
+  where
+    F: FnMut(Node<'_>) -> bool,
+  {
+    let root = unsafe { tidyGetRoot(self.tdoc) };
+    node::discard_nodes(self, root, &mut discard)
+  }
+
+  /// Discard all attributes matching a predicate from the nodes of the document tree.
+  ///
+  /// ```
+  /// # use tidy::*;
+  /// # fn main() -> Result<(), TidyError> {
+  /// let mut tidy = Tidy::new()?;
+  /// tidy.parse_string(b"<title>T</title><p style='color: red' class='c'>Text".to_vec())?;
+  /// tidy.clean_and_repair()?;
+  ///
+  /// assert_eq!(tidy.discard_attrs(|attr| attr.id() == TidyAttrId::TidyAttr_STYLE), 1);
+  /// tidy.save_buffer()?;
+  /// assert!(tidy.output().to_string_lossy().contains("<p class=\"c\">"));
+  /// # Ok(())
+  /// # }
+  /// ```
+  ///
+  /// # Returns
+  /// The number of attributes discarded.
+  pub fn discard_attrs<F>(&mut self, mut discard: F) -> usize
+  where
+    F: FnMut(Attr<'_>) -> bool,
+  {
+    let root = unsafe { tidyGetRoot(self.tdoc) };
+    node::discard_attrs(self, root, &mut discard)
   }
 
   // Document save functions
@@ -822,14 +1735,16 @@ impl Tidy {
   /// **cfgfil**	The filename to save the configuration to.
   pub fn opt_save_file(&self, cfgfil: &str) -> Result<TidySeverity, TidyError> {
     unsafe {
-      let c_cfgfil = CString::new(cfgfil).unwrap();
+      let mark = self.diagnostics_mark();
+      let c_cfgfil = c_string(cfgfil)?;
       match tidyOptSaveFile(self.tdoc, c_cfgfil.as_ptr()) {
         0 => Ok(TidySeverity::Success),
         1 => Ok(TidySeverity::Warning),
         2 => Ok(TidySeverity::Error),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy save option file error"),
+        rc => Err(TidyError::Config {
+          path: Some(cfgfil.to_string()),
+          status: Some(rc),
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
     }
@@ -838,18 +1753,80 @@ impl Tidy {
   ///
   /// # Returns
   /// A TidySeverity or a TidyError representing the status.
-  pub fn save_buffer(&self) -> Result<TidySeverity, TidyError> {
-    unsafe {
-      match tidySaveBuffer(self.tdoc, self.output) {
+  pub fn save_buffer(&mut self) -> Result<TidySeverity, TidyError> {
+    panic::guard(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
+      match tidySaveBuffer(self.tdoc, self.output.as_ptr()) {
         0 => Ok(TidySeverity::Success),
         1 => Ok(TidySeverity::Warning),
         2 => Ok(TidySeverity::Error),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy save buffer error"),
+        rc => Err(TidyError::Save {
+          path: None,
+          status: rc,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
+  }
+
+  /// Save the tidy document to the output buffer, recording where the nodes of the input were written.
+  ///
+  /// # Returns
+  /// The status together with the mappings reported by tidy's pretty printer, or a TidyError.
+  pub fn save_buffer_with_source_map(&mut self) -> Result<(TidySeverity, SourceMap), TidyError> {
+    *self.context().source_map.borrow_mut() = Some(Vec::new());
+    let result = self.save_buffer();
+    let mappings = self.context().source_map.borrow_mut().take();
+    result.map(|status| (status, SourceMap::new(mappings.unwrap_or_default())))
+  }
+
+  /// Save the tidy document to a writer.
+  ///
+  /// Output is written in chunks while tidy serializes the document, the writer is flushed at the end.
+  ///
+  /// # Returns
+  /// A TidySeverity or a TidyError representing the status. A write error is returned as TidyError::Io.
+  /// # Parameters
+  /// **writer**	The destination writer.
+  ///
+  /// ```
+  /// # use tidy::*;
+  /// # fn main() -> Result<(), TidyError> {
+  /// let mut tidy = Tidy::new()?;
+  /// tidy.parse_bytes(b"<title>T</title><p>To a writer")?;
+  /// let mut out = Vec::new();
+  /// tidy.save_writer(&mut out)?;
+  /// assert!(String::from_utf8_lossy(&out).contains("<p>To a writer</p>"));
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn save_writer<W: Write>(&mut self, writer: W) -> Result<TidySeverity, TidyError> {
+    panic::guard(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
+      let mut writer = WriterSink::new(writer);
+      let mut sink: TidyOutputSink = std::mem::zeroed();
+      let rc = if writer.init(&mut sink) {
+        tidySaveSink(self.tdoc, &mut sink)
+      } else {
+        -EINVAL
+      };
+      if let Err(error) = writer.finish() {
+        return Err(TidyError::Io {
+          path: None,
+          error: Arc::new(error),
+        });
+      }
+      match rc {
+        0 => Ok(TidySeverity::Success),
+        1 => Ok(TidySeverity::Warning),
+        2 => Ok(TidySeverity::Error),
+        rc => Err(TidyError::Save {
+          path: None,
+          status: rc,
+          diagnostics: self.diagnostics_since(mark),
+        }),
+      }
+    })
   }
 
   /// Save the tidy document to the named file.
@@ -858,55 +1835,64 @@ impl Tidy {
   /// A TidySeverity or a TidyError representing the status.
   /// # Parameters
   /// **filename**	The destination file name.
-  pub fn save_file(&self, filename: &str) -> Result<TidySeverity, TidyError> {
-    unsafe {
-      let c_filename = CString::new(filename).unwrap();
+  pub fn save_file(&mut self, filename: &str) -> Result<TidySeverity, TidyError> {
+    panic::guard(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
+      let c_filename = c_string(filename)?;
       match tidySaveFile(self.tdoc, c_filename.as_ptr()) {
         0 => Ok(TidySeverity::Success),
         1 => Ok(TidySeverity::Warning),
         2 => Ok(TidySeverity::Error),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy save file error"),
+        rc if rc == -ENOENT => Err(TidyError::io(filename, rc)),
+        rc => Err(TidyError::Save {
+          path: Some(filename.to_string()),
+          status: rc,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 
   /// Save the tidy document to standard output (FILE*).
   ///
   /// # Returns
   /// A TidySeverity or a TidyError representing the status.
-  pub fn save_stdout(&self) -> Result<TidySeverity, TidyError> {
-    unsafe {
+  pub fn save_stdout(&mut self) -> Result<TidySeverity, TidyError> {
+    panic::guard(self.context, || unsafe {
+      let mark = self.diagnostics_mark();
       match tidySaveStdout(self.tdoc) {
         0 => Ok(TidySeverity::Success),
         1 => Ok(TidySeverity::Warning),
         2 => Ok(TidySeverity::Error),
-        _ => Err(TidyError {
-          severity: TidySeverity::Severe,
-          message: String::from("Tidy save stdout error"),
+        rc => Err(TidyError::Save {
+          path: None,
+          status: rc,
+          diagnostics: self.diagnostics_since(mark),
         }),
       }
-    }
+    })
   }
 }
 
 impl Drop for Tidy {
   fn drop(&mut self) {
     unsafe {
-      //println! {"{:?}", *self.errbuf}
-      if !(*self.errbuf).bp.is_null() {
-        tidyBufFree(self.errbuf);
-      }
-      Box::from_raw(self.errbuf);
-      //println! {"{:?}", *self.errbuf}
-      if !(*self.output).bp.is_null() {
-        tidyBufFree(self.output);
-      }
-      Box::from_raw(self.output);
-      //println! {"{:?}", *self.output}
-      tidyRelease(self.tdoc);
+      // After a libtidy panic the document is in an undefined state, it is leaked unless its memory can be freed
+      // through its allocator.
+      let poisoned = self.context().poison.borrow().is_some();
+      if !poisoned {
+        tidyRelease(self.tdoc);
+      }
+      // The buffers allocate through the allocator as well, so they go first.
+      drop(std::mem::take(&mut self.errbuf));
+      drop(std::mem::take(&mut self.output));
+      if poisoned && !self.allocator.is_null() {
+        (*self.allocator).release_all();
+      }
+      if !self.allocator.is_null() {
+        drop(Box::from_raw(self.allocator));
+      }
+      drop(Box::from_raw(self.context));
     }
   }
 }
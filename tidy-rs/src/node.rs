@@ -0,0 +1,415 @@
+use crate::{
+  Attr, AttrMut, Attributes, Buffer, Tidy, TidyAttrId, TidyNodeType, TidyTagId, TidyUtil,
+};
+use libc::*;
+use std::fmt;
+use tidy_sys::*;
+
+/// A node of the document tree owned by a [`Tidy`] instance.
+///
+/// Nodes are cheap, copyable handles borrowed from the document, so they can't outlive it. Parsing, repairing and
+/// saving may free nodes, e.g. saving with hide-comments drops the comments, so none of them can be called while any
+/// handle is alive.
+///
+/// ```
+/// # use tidy::*;
+/// # fn main() -> Result<(), TidyError> {
+/// let mut tidy = Tidy::new()?;
+/// tidy.parse_string(b"<title>T</title><p>One<p>Two".to_vec())?;
+/// tidy.clean_and_repair()?;
+///
+/// let body = tidy.body().unwrap();
+/// let names: Vec<_> = body.children().filter_map(|n| n.name()).collect();
+/// assert_eq!(names, vec!["p", "p"]);
+/// assert_eq!(body.parent(), tidy.html());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct Node<'doc> {
+  tidy: &'doc Tidy,
+  tnod: TidyNode,
+}
+
+impl<'doc> Node<'doc> {
+  pub(crate) fn from_raw(tidy: &'doc Tidy, tnod: TidyNode) -> Option<Node<'doc>> {
+    if tnod.is_null() {
+      None
+    } else {
+      Some(Node { tidy, tnod })
+    }
+  }
+
+  /// Get the parent of this node.
+  ///
+  /// # Returns
+  /// The parent node, or None for the document root.
+  pub fn parent(&self) -> Option<Node<'doc>> {
+    Node::from_raw(self.tidy, unsafe { tidyGetParent(self.tnod) })
+  }
+
+  /// Get the first child of this node.
+  ///
+  /// # Returns
+  /// The first child node, or None if this node has no children.
+  pub fn first_child(&self) -> Option<Node<'doc>> {
+    Node::from_raw(self.tidy, unsafe { tidyGetChild(self.tnod) })
+  }
+
+  /// Get the next sibling of this node.
+  ///
+  /// # Returns
+  /// The next sibling node, or None if this is the last child of its parent.
+  pub fn next_sibling(&self) -> Option<Node<'doc>> {
+    Node::from_raw(self.tidy, unsafe { tidyGetNext(self.tnod) })
+  }
+
+  /// Get the previous sibling of this node.
+  ///
+  /// # Returns
+  /// The previous sibling node, or None if this is the first child of its parent.
+  pub fn prev_sibling(&self) -> Option<Node<'doc>> {
+    Node::from_raw(self.tidy, unsafe { tidyGetPrev(self.tnod) })
+  }
+
+  /// Iterate over the children of this node in document order.
+  pub fn children(&self) -> Siblings<'doc> {
+    Siblings {
+      next: self.first_child(),
+    }
+  }
+
+  /// Iterate over the siblings following this node in document order.
+  pub fn siblings(&self) -> Siblings<'doc> {
+    Siblings {
+      next: self.next_sibling(),
+    }
+  }
+
+  /// Iterate over the attributes of this node in document order.
+  pub fn attributes(&self) -> Attributes<'doc> {
+    Attributes {
+      next: Attr::from_raw(*self, unsafe { tidyAttrFirst(self.tnod) }),
+    }
+  }
+
+  /// Get an attribute of this node by name.
+  ///
+  /// # Parameters
+  /// **name**	The attribute name, matched exactly against the names in the repaired document.
+  /// # Returns
+  /// The first attribute with the given name, or None if there is none.
+  pub fn attr(&self, name: &str) -> Option<Attr<'doc>> {
+    self.attributes().find(|attr| attr.name() == name)
+  }
+
+  /// Get an attribute of this node by id.
+  ///
+  /// # Parameters
+  /// **attid**	The TidyAttrId of the attribute.
+  /// # Returns
+  /// The attribute with the given id, or None if there is none.
+  pub fn attr_by_id(&self, attid: TidyAttrId) -> Option<Attr<'doc>> {
+    Attr::from_raw(*self, unsafe { tidyAttrGetById(self.tnod, attid) })
+  }
+
+  /// Get the name of this node.
+  ///
+  /// # Returns
+  /// The element name, or None for nodes without one, e.g. text nodes.
+  pub fn name(&self) -> Option<String> {
+    unsafe {
+      let nnam = tidyNodeGetName(self.tnod);
+      if nnam.is_null() {
+        None
+      } else {
+        Some(TidyUtil::c_str_to_owned(nnam))
+      }
+    }
+  }
+
+  /// Get the type of this node.
+  ///
+  /// # Returns
+  /// The TidyNodeType of this node.
+  pub fn node_type(&self) -> TidyNodeType {
+    unsafe { tidyNodeGetType(self.tnod) }
+  }
+
+  /// Get the tag id of this node.
+  ///
+  /// # Returns
+  /// The TidyTagId of this node, TidyTag_UNKNOWN for nodes that aren't known HTML elements.
+  pub fn tag_id(&self) -> TidyTagId {
+    unsafe { tidyNodeGetId(self.tnod) }
+  }
+
+  /// Get the line number of this node in the input document.
+  ///
+  /// # Returns
+  /// The line number, starting at 1.
+  pub fn line(&self) -> c_uint {
+    unsafe { tidyNodeLine(self.tnod) }
+  }
+
+  /// Get the column of this node in the input document.
+  ///
+  /// # Returns
+  /// The column number, starting at 1.
+  pub fn column(&self) -> c_uint {
+    unsafe { tidyNodeColumn(self.tnod) }
+  }
+
+  /// Indicates whether or not this is a text node.
+  pub fn is_text(&self) -> bool {
+    unsafe { TidyUtil::tidy_bool_to_bool(tidyNodeIsText(self.tnod)) }
+  }
+
+  /// Indicates whether or not this is a heading element (h1 to h6).
+  pub fn is_header(&self) -> bool {
+    unsafe { TidyUtil::tidy_bool_to_bool(tidyNodeIsHeader(self.tnod)) }
+  }
+
+  /// Indicates whether or not this is a proprietary element.
+  pub fn is_prop(&self) -> bool {
+    unsafe { TidyUtil::tidy_bool_to_bool(tidyNodeIsProp(self.tidy.tdoc, self.tnod)) }
+  }
+
+  /// Indicates whether or not this node has text content.
+  pub fn has_text(&self) -> bool {
+    unsafe { TidyUtil::tidy_bool_to_bool(tidyNodeHasText(self.tidy.tdoc, self.tnod)) }
+  }
+
+  /// Get the text of this node as it would be written to the output, including markup.
+  ///
+  /// # Returns
+  /// The pretty printed text, or None if this node has no text.
+  pub fn text(&self) -> Option<Vec<u8>> {
+    self.read_buffer(tidyNodeGetText)
+  }
+
+  /// Get the raw value of a text, comment, CDATA or similar node, without any markup.
+  ///
+  /// # Returns
+  /// The raw bytes of the value, or None if this node has no value.
+  pub fn value(&self) -> Option<Vec<u8>> {
+    self.read_buffer(tidyNodeGetValue)
+  }
+
+  fn read_buffer(
+    &self,
+    read: unsafe extern "C-unwind" fn(TidyDoc, TidyNode, *mut TidyBuffer) -> Bool,
+  ) -> Option<Vec<u8>> {
+    let buf = Buffer::new();
+    let rc = unsafe { read(self.tidy.tdoc, self.tnod, buf.as_ptr()) };
+    if TidyUtil::tidy_bool_to_bool(rc) {
+      Some(buf.into_vec())
+    } else {
+      None
+    }
+  }
+}
+
+impl<'doc> PartialEq for Node<'doc> {
+  fn eq(&self, other: &Self) -> bool {
+    self.tnod == other.tnod
+  }
+}
+
+impl<'doc> Eq for Node<'doc> {}
+
+impl<'doc> fmt::Debug for Node<'doc> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Node")
+      .field("name", &self.name())
+      .field("node_type", &self.node_type())
+      .field("line", &self.line())
+      .field("column", &self.column())
+      .finish()
+  }
+}
+
+/// Iterator over a run of sibling nodes, see [`Node::children`] and [`Node::siblings`].
+#[derive(Clone)]
+pub struct Siblings<'doc> {
+  next: Option<Node<'doc>>,
+}
+
+impl<'doc> Iterator for Siblings<'doc> {
+  type Item = Node<'doc>;
+
+  fn next(&mut self) -> Option<Node<'doc>> {
+    let node = self.next.take()?;
+    self.next = node.next_sibling();
+    Some(node)
+  }
+}
+
+/// A mutable handle to a node of the document tree, see [`Tidy::root_mut`].
+///
+/// The handle borrows the Tidy instance exclusively, so no [`Node`] or [`Attr`] can be alive while the tree is
+/// changed through it. Navigation consumes the handle and returns the handle of the target node.
+///
+/// ```
+/// # use tidy::*;
+/// # fn main() -> Result<(), TidyError> {
+/// let mut tidy = Tidy::new()?;
+/// tidy.parse_string(b"<title>T</title><script>track()</script><p style='color: red'>Text".to_vec())?;
+/// tidy.clean_and_repair()?;
+///
+/// let script = tidy.body_mut().unwrap().first_child().unwrap();
+/// assert_eq!(script.node().name().as_deref(), Some("script"));
+/// let mut p = script.discard().unwrap();
+/// p.attr("style").unwrap().discard();
+///
+/// tidy.save_buffer()?;
+/// let output = tidy.output().to_string_lossy();
+/// assert!(!output.contains("track()") && !output.contains("color: red"));
+/// assert!(output.contains("<p>Text</p>"));
+/// # Ok(())
+/// # }
+/// ```
+pub struct NodeMut<'doc> {
+  tidy: &'doc mut Tidy,
+  tnod: TidyNode,
+}
+
+impl<'doc> NodeMut<'doc> {
+  pub(crate) fn from_raw(tidy: &'doc mut Tidy, tnod: TidyNode) -> Option<NodeMut<'doc>> {
+    if tnod.is_null() {
+      None
+    } else {
+      Some(NodeMut { tidy, tnod })
+    }
+  }
+
+  /// Get a read-only handle to this node, valid until the tree is changed again.
+  pub fn node(&self) -> Node<'_> {
+    Node {
+      tidy: self.tidy,
+      tnod: self.tnod,
+    }
+  }
+
+  /// Move to the parent of this node.
+  ///
+  /// # Returns
+  /// The parent node, or None for the document root.
+  pub fn parent(self) -> Option<NodeMut<'doc>> {
+    let tnod = unsafe { tidyGetParent(self.tnod) };
+    NodeMut::from_raw(self.tidy, tnod)
+  }
+
+  /// Move to the first child of this node.
+  ///
+  /// # Returns
+  /// The first child node, or None if this node has no children.
+  pub fn first_child(self) -> Option<NodeMut<'doc>> {
+    let tnod = unsafe { tidyGetChild(self.tnod) };
+    NodeMut::from_raw(self.tidy, tnod)
+  }
+
+  /// Move to the next sibling of this node.
+  ///
+  /// # Returns
+  /// The next sibling node, or None if this is the last child of its parent.
+  pub fn next_sibling(self) -> Option<NodeMut<'doc>> {
+    let tnod = unsafe { tidyGetNext(self.tnod) };
+    NodeMut::from_raw(self.tidy, tnod)
+  }
+
+  /// Move to the previous sibling of this node.
+  ///
+  /// # Returns
+  /// The previous sibling node, or None if this is the first child of its parent.
+  pub fn prev_sibling(self) -> Option<NodeMut<'doc>> {
+    let tnod = unsafe { tidyGetPrev(self.tnod) };
+    NodeMut::from_raw(self.tidy, tnod)
+  }
+
+  /// Get a mutable handle to an attribute of this node by name.
+  ///
+  /// # Parameters
+  /// **name**	The attribute name, matched exactly against the names in the repaired document.
+  /// # Returns
+  /// The first attribute with the given name, or None if there is none.
+  pub fn attr(&mut self, name: &str) -> Option<AttrMut<'_>> {
+    let tattr = self.node().attr(name)?.tattr;
+    AttrMut::from_raw(self.tidy, self.tnod, tattr)
+  }
+
+  /// Get a mutable handle to an attribute of this node by id.
+  ///
+  /// # Parameters
+  /// **attid**	The TidyAttrId of the attribute.
+  /// # Returns
+  /// The attribute with the given id, or None if there is none.
+  pub fn attr_by_id(&mut self, attid: TidyAttrId) -> Option<AttrMut<'_>> {
+    let tattr = unsafe { tidyAttrGetById(self.tnod, attid) };
+    AttrMut::from_raw(self.tidy, self.tnod, tattr)
+  }
+
+  /// Remove this node and its children from the document tree.
+  ///
+  /// The document root can't be discarded, discarding it leaves the tree unchanged.
+  ///
+  /// # Returns
+  /// The next sibling of the discarded node, or None if it was the last child of its parent.
+  pub fn discard(self) -> Option<NodeMut<'doc>> {
+    unsafe {
+      if tidyGetParent(self.tnod).is_null() {
+        return None;
+      }
+      let next = tidyDiscardElement(self.tidy.tdoc, self.tnod);
+      NodeMut::from_raw(self.tidy, next)
+    }
+  }
+}
+
+impl<'doc> fmt::Debug for NodeMut<'doc> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_tuple("NodeMut").field(&self.node()).finish()
+  }
+}
+
+/// Discard the descendants of a node matching the predicate, see [`Tidy::discard_nodes`].
+pub(crate) fn discard_nodes<F>(tidy: &mut Tidy, parent: TidyNode, discard: &mut F) -> usize
+where
+  F: FnMut(Node<'_>) -> bool,
+{
+  let mut count = 0;
+  let mut tnod = unsafe { tidyGetChild(parent) };
+  while !tnod.is_null() {
+    if discard(Node { tidy, tnod }) {
+      tnod = unsafe { tidyDiscardElement(tidy.tdoc, tnod) };
+      count += 1;
+    } else {
+      count += discard_nodes(tidy, tnod, discard);
+      tnod = unsafe { tidyGetNext(tnod) };
+    }
+  }
+  count
+}
+
+/// Discard the attributes of a node and its descendants matching the predicate, see [`Tidy::discard_attrs`].
+pub(crate) fn discard_attrs<F>(tidy: &mut Tidy, tnod: TidyNode, discard: &mut F) -> usize
+where
+  F: FnMut(Attr<'_>) -> bool,
+{
+  let mut count = 0;
+  let mut tattr = unsafe { tidyAttrFirst(tnod) };
+  while !tattr.is_null() {
+    let next = unsafe { tidyAttrNext(tattr) };
+    if discard(Attr::from_raw(Node { tidy, tnod }, tattr).unwrap()) {
+      unsafe { tidyAttrDiscard(tidy.tdoc, tnod, tattr) };
+      count += 1;
+    }
+    tattr = next;
+  }
+  let mut child = unsafe { tidyGetChild(tnod) };
+  while !child.is_null() {
+    count += discard_attrs(tidy, child, discard);
+    child = unsafe { tidyGetNext(child) };
+  }
+  count
+}
@@ -0,0 +1,350 @@
+//! Typed configuration options.
+//!
+//! Every tidy option is represented by a unit struct implementing [`TidyOpt`], which fixes the Rust type of its
+//! value. Boolean options take a `bool`, numeric options a `c_ulong`, string options a `String` and pick list options
+//! one of the enums in this module, so [`Tidy::opt_set`] and [`Tidy::opt_get`] can't be called with a value of the
+//! wrong type.
+//!
+//! ```
+//! # use tidy::*;
+//! # fn main() -> Result<(), TidyError> {
+//! let tidy = Tidy::new()?;
+//! tidy.opt_set(opt::IndentContent, opt::TriState::Auto)?;
+//! tidy.opt_set(opt::IndentSpaces, 4)?;
+//! tidy.opt_set(opt::AltText, "image".into())?;
+//! tidy.opt_set(opt::CharEncoding, opt::Encoding::Utf8)?;
+//!
+//! assert_eq!(tidy.opt_get(opt::IndentSpaces), 4);
+//! assert_eq!(tidy.opt_get(opt::IndentContent), opt::TriState::Auto);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Tidy, TidyOptionId, TidyUtil};
+use libc::*;
+use std::fmt;
+use tidy_sys::*;
+
+/// A tidy configuration option with a statically known value type.
+pub trait TidyOpt: Copy {
+  /// The id of the option.
+  const ID: TidyOptionId;
+  /// The Rust type of the option value.
+  type Value: OptValue;
+}
+
+/// A Rust type that can be used as the value of a tidy option.
+pub trait OptValue: Sized {
+  /// Convert the value to its representation in tidy config files.
+  fn to_opt_value(&self) -> String;
+  /// Read the current value of the option from the given document.
+  fn from_tidy(tidy: &Tidy, optid: TidyOptionId) -> Self;
+}
+
+impl OptValue for bool {
+  fn to_opt_value(&self) -> String {
+    String::from(if *self { "yes" } else { "no" })
+  }
+
+  fn from_tidy(tidy: &Tidy, optid: TidyOptionId) -> bool {
+    unsafe { tidyOptGetInt(tidy.tdoc, optid) != 0 }
+  }
+}
+
+impl OptValue for c_ulong {
+  fn to_opt_value(&self) -> String {
+    self.to_string()
+  }
+
+  fn from_tidy(tidy: &Tidy, optid: TidyOptionId) -> c_ulong {
+    unsafe { tidyOptGetInt(tidy.tdoc, optid) }
+  }
+}
+
+impl OptValue for String {
+  fn to_opt_value(&self) -> String {
+    self.clone()
+  }
+
+  fn from_tidy(tidy: &Tidy, optid: TidyOptionId) -> String {
+    unsafe {
+      let val = tidyOptGetValue(tidy.tdoc, optid);
+      if val.is_null() {
+        String::new()
+      } else {
+        TidyUtil::c_str_to_owned(val)
+      }
+    }
+  }
+}
+
+macro_rules! pick_list {
+  ($(#[$meta:meta])* $name:ident { $($(#[$vmeta:meta])* $variant:ident = $pick:literal,)* }) => {
+    $(#[$meta])*
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum $name {
+      $($(#[$vmeta])* $variant,)*
+    }
+
+    impl $name {
+      /// All values in the order of the tidy pick list.
+      pub const ALL: &'static [$name] = &[$($name::$variant,)*];
+
+      /// Get the value as written in tidy config files.
+      pub fn as_str(&self) -> &'static str {
+        match self {
+          $($name::$variant => $pick,)*
+        }
+      }
+
+      /// Get the value for a pick list entry, ignoring case.
+      pub fn from_pick(pick: &str) -> Option<$name> {
+        $name::ALL.iter().copied().find(|v| v.as_str().eq_ignore_ascii_case(pick))
+      }
+    }
+
+    impl fmt::Display for $name {
+      fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+      }
+    }
+  };
+}
+
+macro_rules! pick_value {
+  ($($name:ident),*) => {
+    $(
+      impl OptValue for $name {
+        fn to_opt_value(&self) -> String {
+          String::from(self.as_str())
+        }
+
+        fn from_tidy(tidy: &Tidy, optid: TidyOptionId) -> $name {
+          let pick = unsafe { tidyOptGetCurrPick(tidy.tdoc, optid) };
+          if !pick.is_null() {
+            if let Some(v) = $name::from_pick(&TidyUtil::c_str_to_owned(pick)) {
+              return v;
+            }
+          }
+          let index = unsafe { tidyOptGetInt(tidy.tdoc, optid) } as usize;
+          $name::ALL.get(index).copied().unwrap_or($name::ALL[0])
+        }
+      }
+    )*
+  };
+}
+
+pick_list!(
+  /// Value of options that can be switched on, off or left to tidy.
+  TriState {
+    No = "no",
+    Yes = "yes",
+    Auto = "auto",
+  }
+);
+
+pick_list!(
+  /// Character encodings understood by tidy.
+  Encoding {
+    Raw = "raw",
+    Ascii = "ascii",
+    Latin0 = "latin0",
+    Latin1 = "latin1",
+    Utf8 = "utf8",
+    Iso2022 = "iso2022",
+    Mac = "mac",
+    Win1252 = "win1252",
+    Ibm858 = "ibm858",
+    Utf16le = "utf16le",
+    Utf16be = "utf16be",
+    Utf16 = "utf16",
+    Big5 = "big5",
+    Shiftjis = "shiftjis",
+  }
+);
+
+pick_list!(
+  /// Which of several duplicate attributes to keep.
+  DupAttrs {
+    KeepFirst = "keep-first",
+    KeepLast = "keep-last",
+  }
+);
+
+pick_list!(
+  /// Line ending used in the output.
+  LineEnding {
+    Lf = "LF",
+    CrLf = "CRLF",
+    Cr = "CR",
+  }
+);
+
+pick_list!(
+  /// How attributes are sorted in the output.
+  AttrSort {
+    None = "none",
+    Alpha = "alpha",
+  }
+);
+
+pick_list!(
+  /// Case of attribute names in the output.
+  AttrCase {
+    No = "no",
+    Yes = "yes",
+    Preserve = "preserve",
+  }
+);
+
+pick_list!(
+  /// How autonomous custom tags are treated.
+  CustomTagsMode {
+    No = "no",
+    BlockLevel = "blocklevel",
+    Empty = "empty",
+    Inline = "inline",
+    Pre = "pre",
+  }
+);
+
+pick_value!(
+  TriState,
+  DupAttrs,
+  LineEnding,
+  AttrSort,
+  AttrCase,
+  CustomTagsMode
+);
+
+impl OptValue for Encoding {
+  fn to_opt_value(&self) -> String {
+    String::from(self.as_str())
+  }
+
+  fn from_tidy(tidy: &Tidy, optid: TidyOptionId) -> Encoding {
+    let name = unsafe { tidyOptGetEncName(tidy.tdoc, optid) };
+    if name.is_null() {
+      return Encoding::Raw;
+    }
+    Encoding::from_pick(&TidyUtil::c_str_to_owned(name)).unwrap_or(Encoding::Raw)
+  }
+}
+
+macro_rules! options {
+  ($($name:ident: $ty:ty = $id:ident, $doc:literal;)*) => {
+    $(
+      #[doc = $doc]
+      #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+      pub struct $name;
+
+      impl TidyOpt for $name {
+        const ID: TidyOptionId = TidyOptionId::$id;
+        type Value = $ty;
+      }
+    )*
+  };
+}
+
+options! {
+  AccessibilityCheckLevel: c_ulong = TidyAccessibilityCheckLevel, "`accessibility-check`: accessibility check level, 0 to 3.";
+  AltText: String = TidyAltText, "`alt-text`: default text for alt attributes.";
+  AnchorAsName: bool = TidyAnchorAsName, "`anchor-as-name`: define anchors as name attributes.";
+  AsciiChars: bool = TidyAsciiChars, "`ascii-chars`: convert quotes and dashes to nearest ASCII character.";
+  BlockTags: String = TidyBlockTags, "`new-blocklevel-tags`: declared block tags.";
+  BodyOnly: TriState = TidyBodyOnly, "`show-body-only`: output body content only.";
+  BreakBeforeBR: bool = TidyBreakBeforeBR, "`break-before-br`: output a newline before `<br>`.";
+  CharEncoding: Encoding = TidyCharEncoding, "`char-encoding`: input and output character encoding.";
+  CoerceEndTags: bool = TidyCoerceEndTags, "`coerce-endtags`: coerce end tags from start tags where probably intended.";
+  CSSPrefix: String = TidyCSSPrefix, "`css-prefix`: CSS class naming for the clean option.";
+  DecorateInferredUL: bool = TidyDecorateInferredUL, "`decorate-inferred-ul`: mark inferred ul elements with no indent CSS.";
+  Doctype: String = TidyDoctype, "`doctype`: user specified doctype.";
+  DropEmptyElems: bool = TidyDropEmptyElems, "`drop-empty-elements`: discard empty elements.";
+  DropEmptyParas: bool = TidyDropEmptyParas, "`drop-empty-paras`: discard empty p elements.";
+  DropPropAttrs: bool = TidyDropPropAttrs, "`drop-proprietary-attributes`: discard proprietary attributes.";
+  DuplicateAttrs: DupAttrs = TidyDuplicateAttrs, "`repeated-attributes`: keep first or last duplicate attribute.";
+  Emacs: bool = TidyEmacs, "`gnu-emacs`: format error output for GNU Emacs.";
+  EmptyTags: String = TidyEmptyTags, "`new-empty-tags`: declared empty tags.";
+  EncloseBlockText: bool = TidyEncloseBlockText, "`enclose-block-text`: wrap text in blocks in p elements.";
+  EncloseBodyText: bool = TidyEncloseBodyText, "`enclose-text`: wrap text at body level in p elements.";
+  ErrFile: String = TidyErrFile, "`error-file`: file name to write errors to.";
+  EscapeCdata: bool = TidyEscapeCdata, "`escape-cdata`: replace CDATA sections with escaped text.";
+  EscapeScripts: bool = TidyEscapeScripts, "`escape-scripts`: escape items that look like closing tags in scripts.";
+  FixBackslash: bool = TidyFixBackslash, "`fix-backslash`: fix URLs by replacing `\\` with `/`.";
+  FixComments: TriState = TidyFixComments, "`fix-bad-comments`: fix comments with adjacent hyphens.";
+  FixUri: bool = TidyFixUri, "`fix-uri`: apply URI encoding if necessary.";
+  ForceOutput: bool = TidyForceOutput, "`force-output`: output the document even if errors were found.";
+  GDocClean: bool = TidyGDocClean, "`gdoc`: clean up HTML exported from Google Docs.";
+  HideComments: bool = TidyHideComments, "`hide-comments`: hide all comments in the output.";
+  HtmlOut: bool = TidyHtmlOut, "`output-html`: output plain HTML, even for XHTML input.";
+  InCharEncoding: Encoding = TidyInCharEncoding, "`input-encoding`: input character encoding.";
+  IndentAttributes: bool = TidyIndentAttributes, "`indent-attributes`: newline and indent before each attribute.";
+  IndentCdata: bool = TidyIndentCdata, "`indent-cdata`: indent CDATA sections.";
+  IndentContent: TriState = TidyIndentContent, "`indent`: indent the content of appropriate tags.";
+  IndentSpaces: c_ulong = TidyIndentSpaces, "`indent-spaces`: number of spaces or tabs to indent by.";
+  InlineTags: String = TidyInlineTags, "`new-inline-tags`: declared inline tags.";
+  JoinClasses: bool = TidyJoinClasses, "`join-classes`: join multiple class attributes.";
+  JoinStyles: bool = TidyJoinStyles, "`join-styles`: join multiple style attributes.";
+  KeepFileTimes: bool = TidyKeepFileTimes, "`keep-time`: preserve the last modified time of tidied files.";
+  KeepTabs: bool = TidyKeepTabs, "`keep-tabs`: keep tabs from the input.";
+  LiteralAttribs: bool = TidyLiteralAttribs, "`literal-attributes`: keep whitespace in attribute values.";
+  LogicalEmphasis: bool = TidyLogicalEmphasis, "`logical-emphasis`: replace i by em and b by strong.";
+  LowerLiterals: bool = TidyLowerLiterals, "`lower-literals`: fold known attribute values to lower case.";
+  MakeBare: bool = TidyMakeBare, "`bare`: replace smart quotes, em dashes etc. with ASCII.";
+  MakeClean: bool = TidyMakeClean, "`clean`: replace presentational clutter by style rules.";
+  Mark: bool = TidyMark, "`tidy-mark`: add a meta element indicating the document was tidied.";
+  MergeDivs: TriState = TidyMergeDivs, "`merge-divs`: merge nested div elements.";
+  MergeEmphasis: bool = TidyMergeEmphasis, "`merge-emphasis`: merge nested b and i elements.";
+  MergeSpans: TriState = TidyMergeSpans, "`merge-spans`: merge nested span elements.";
+  MetaCharset: bool = TidyMetaCharset, "`add-meta-charset`: add or fix the meta charset element.";
+  MuteReports: String = TidyMuteReports, "`mute`: message keys to filter from the output.";
+  MuteShow: bool = TidyMuteShow, "`mute-id`: show message keys in the output.";
+  NCR: bool = TidyNCR, "`ncr`: allow numeric character references.";
+  Newline: LineEnding = TidyNewline, "`newline`: line ending of the output.";
+  NumEntities: bool = TidyNumEntities, "`numeric-entities`: output entities as numeric references.";
+  OmitOptionalTags: bool = TidyOmitOptionalTags, "`omit-optional-tags`: omit optional start and end tags.";
+  OutCharEncoding: Encoding = TidyOutCharEncoding, "`output-encoding`: output character encoding.";
+  OutFile: String = TidyOutFile, "`output-file`: file name to write markup to.";
+  OutputBOM: TriState = TidyOutputBOM, "`output-bom`: output a byte order mark.";
+  PPrintTabs: bool = TidyPPrintTabs, "`indent-with-tabs`: indent using tabs instead of spaces.";
+  PreserveEntities: bool = TidyPreserveEntities, "`preserve-entities`: preserve well formed entities.";
+  PreTags: String = TidyPreTags, "`new-pre-tags`: declared pre tags.";
+  PriorityAttributes: String = TidyPriorityAttributes, "`priority-attributes`: attributes to place first in an element.";
+  PunctWrap: bool = TidyPunctWrap, "`punctuation-wrap`: wrap after punctuation and breaking spaces.";
+  Quiet: bool = TidyQuiet, "`quiet`: suppress non-document messages.";
+  QuoteAmpersand: bool = TidyQuoteAmpersand, "`quote-ampersand`: output naked ampersands as `&amp;`.";
+  QuoteMarks: bool = TidyQuoteMarks, "`quote-marks`: output `\"` as `&quot;`.";
+  QuoteNbsp: bool = TidyQuoteNbsp, "`quote-nbsp`: output non-breaking spaces as entities.";
+  ReplaceColor: bool = TidyReplaceColor, "`replace-color`: replace hex colors with names.";
+  ShowErrors: c_ulong = TidyShowErrors, "`show-errors`: number of errors to report.";
+  ShowFilename: bool = TidyShowFilename, "`show-filename`: show the input file name with messages.";
+  ShowInfo: bool = TidyShowInfo, "`show-info`: report info level messages.";
+  ShowMarkup: bool = TidyShowMarkup, "`markup`: output the tidied markup.";
+  ShowMetaChange: bool = TidyShowMetaChange, "`show-meta-change`: report changes to the meta charset.";
+  ShowWarnings: bool = TidyShowWarnings, "`show-warnings`: report warnings.";
+  SkipNested: bool = TidySkipNested, "`skip-nested`: skip nested tags in script and style.";
+  SortAttributes: AttrSort = TidySortAttributes, "`sort-attributes`: sort attributes in the output.";
+  StrictTagsAttr: bool = TidyStrictTagsAttr, "`strict-tags-attributes`: check tags and attributes against the output HTML version.";
+  StyleTags: bool = TidyStyleTags, "`fix-style-tags`: move style elements to the head.";
+  TabSize: c_ulong = TidyTabSize, "`tab-size`: number of spaces to expand tabs to.";
+  UpperCaseAttrs: AttrCase = TidyUpperCaseAttrs, "`uppercase-attributes`: case of attribute names in the output.";
+  UpperCaseTags: bool = TidyUpperCaseTags, "`uppercase-tags`: output tags in upper case.";
+  UseCustomTags: CustomTagsMode = TidyUseCustomTags, "`custom-tags`: how to treat autonomous custom tags.";
+  VertSpace: TriState = TidyVertSpace, "`vertical-space`: add vertical whitespace for readability.";
+  WarnPropAttrs: bool = TidyWarnPropAttrs, "`warn-proprietary-attributes`: warn on proprietary attributes.";
+  Word2000: bool = TidyWord2000, "`word-2000`: draconian cleaning for Word 2000 documents.";
+  WrapAsp: bool = TidyWrapAsp, "`wrap-asp`: wrap within ASP pseudo elements.";
+  WrapAttVals: bool = TidyWrapAttVals, "`wrap-attributes`: wrap within attribute values.";
+  WrapJste: bool = TidyWrapJste, "`wrap-jste`: wrap within JSTE pseudo elements.";
+  WrapLen: c_ulong = TidyWrapLen, "`wrap`: wrap margin, 0 disables wrapping.";
+  WrapPhp: bool = TidyWrapPhp, "`wrap-php`: wrap within PHP pseudo elements.";
+  WrapScriptlets: bool = TidyWrapScriptlets, "`wrap-script-literals`: wrap within JavaScript string literals.";
+  WrapSection: bool = TidyWrapSection, "`wrap-sections`: wrap within `<![ ... ]>` section tags.";
+  WriteBack: bool = TidyWriteBack, "`write-back`: write the tidied markup back to the input file.";
+  XhtmlOut: bool = TidyXhtmlOut, "`output-xhtml`: output extensible HTML.";
+  XmlDecl: bool = TidyXmlDecl, "`add-xml-decl`: add an XML declaration to XML output.";
+  XmlOut: bool = TidyXmlOut, "`output-xml`: output well formed XML.";
+  XmlPIs: bool = TidyXmlPIs, "`assume-xml-procins`: processing instructions must end with `?>`.";
+  XmlSpace: bool = TidyXmlSpace, "`add-xml-space`: add `xml:space` attributes as needed.";
+  XmlTags: bool = TidyXmlTags, "`input-xml`: treat the input as XML.";
+}
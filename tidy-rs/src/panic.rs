@@ -0,0 +1,159 @@
+use crate::{TidyContext, TidyError, TidyUtil};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::sync::Once;
+use tidy_sys::*;
+
+/// Payload of the unwind started when libtidy panics.
+pub(crate) struct TidyPanic(pub(crate) String);
+
+/// Payload of the unwind started when an allocation would exceed the memory limit.
+pub(crate) struct MemoryLimit(pub(crate) usize);
+
+/// Payload of a panic caught in a Rust callback called by libtidy.
+pub(crate) type Payload = Box<dyn Any + Send>;
+
+thread_local! {
+  // Number of guard() calls active on this thread.
+  static GUARDED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Abandon the current libtidy call by unwinding to the enclosing guard().
+///
+/// Outside of guard() there is nothing to unwind to, the message is printed and the process aborted.
+pub(crate) fn raise(message: String) -> ! {
+  if !guarded() {
+    eprintln!("libtidy panicked: {}", message);
+    std::process::abort();
+  }
+  resume_unwind(Box::new(TidyPanic(message)))
+}
+
+/// Indicates whether or not a libtidy call on this thread runs within guard(), so it can be unwound.
+pub(crate) fn guarded() -> bool {
+  GUARDED.with(Cell::get) > 0
+}
+
+/// Abandon the current libtidy call because the memory limit would be exceeded, only valid if guarded().
+pub(crate) fn exceed_limit(limit: usize) -> ! {
+  resume_unwind(Box::new(MemoryLimit(limit)))
+}
+
+unsafe extern "C-unwind" fn panic_callback(msg: ctmbstr) {
+  raise(TidyUtil::c_str_to_owned(msg))
+}
+
+/// Run a Rust callback called by libtidy, e.g. a report filter, which must not unwind into libtidy.
+///
+/// A panic is stored in the slot and the fallback returned instead, as it is for all further callbacks until the panic
+/// is resumed once the libtidy call returned.
+pub(crate) fn catch<T, F>(slot: &RefCell<Option<Payload>>, fallback: T, f: F) -> T
+where
+  F: FnOnce() -> T,
+{
+  if slot.borrow().is_some() {
+    return fallback;
+  }
+  match catch_unwind(AssertUnwindSafe(f)) {
+    Ok(value) => value,
+    Err(payload) => {
+      *slot.borrow_mut() = Some(payload);
+      fallback
+    }
+  }
+}
+
+/// Resume the panic of a callback stored in the slot, if any.
+pub(crate) fn resume(slot: &RefCell<Option<Payload>>) {
+  let payload = slot.borrow_mut().take();
+  if let Some(payload) = payload {
+    resume_unwind(payload);
+  }
+}
+
+/// Run a libtidy operation that may call back into Rust outside of guard(), resuming the panic of a callback once it
+/// returned.
+pub(crate) fn resuming<T, F>(context: *mut TidyContext, f: F) -> T
+where
+  F: FnOnce() -> T,
+{
+  let result = f();
+  resume(unsafe { &(*context).callback_panic });
+  result
+}
+
+/// Route the panics of tidy's default allocator to raise().
+///
+/// The default panic function prints the message and exits the process. The replacement is process-wide and unwinds
+/// through the libtidy frames to the guard() of the current thread, which tidy-sys permits by binding libtidy with the
+/// C-unwind ABI and requires libtidy to be built with unwind tables, the default for the common targets.
+pub(crate) fn install() {
+  static INSTALL: Once = Once::new();
+  INSTALL.call_once(|| unsafe {
+    tidySetPanicCall(Some(panic_callback));
+  });
+}
+
+/// Run a libtidy operation, turning a libtidy panic into TidyError::Panic and exceeding the memory limit into
+/// TidyError::MemoryLimit.
+///
+/// The document is left in an undefined state by the unwind, so the instance is poisoned: all further guarded
+/// operations fail with the same error, and the document isn't released through tidy on drop.
+///
+/// The outermost guard on a thread holds a shared lock on tidy's language while the operation runs. The panic of a
+/// callback called by the operation is resumed once it returned.
+pub(crate) fn guard<T, F>(context: *mut TidyContext, f: F) -> Result<T, TidyError>
+where
+  F: FnOnce() -> Result<T, TidyError>,
+{
+  let context = unsafe { &*context };
+  if let Some(poison) = context.poison.borrow().clone() {
+    return Err(poison);
+  }
+  let mark = context.diagnostics.borrow().len();
+  let _language = if guarded() {
+    None
+  } else {
+    Some(crate::read_language())
+  };
+  GUARDED.with(|guarded| guarded.set(guarded.get() + 1));
+  let result = catch_unwind(AssertUnwindSafe(f));
+  GUARDED.with(|guarded| guarded.set(guarded.get() - 1));
+  let payload = match result {
+    Ok(result) => {
+      resume(&context.callback_panic);
+      return result;
+    }
+    Err(payload) => payload,
+  };
+  let diagnostics = context
+    .diagnostics
+    .borrow()
+    .get(mark..)
+    .unwrap_or_default()
+    .to_vec();
+  let error = match payload.downcast::<TidyPanic>() {
+    Ok(panic) => TidyError::Panic {
+      message: panic.0,
+      diagnostics,
+    },
+    Err(payload) => match payload.downcast::<MemoryLimit>() {
+      Ok(limit) => TidyError::MemoryLimit {
+        limit: limit.0,
+        diagnostics,
+      },
+      Err(payload) => resume_unwind(payload),
+    },
+  };
+  // Later calls fail with the same error, without the messages of this call.
+  let mut poison = error.clone();
+  if let TidyError::Panic { diagnostics, .. } | TidyError::MemoryLimit { diagnostics, .. } =
+    &mut poison
+  {
+    diagnostics.clear();
+  }
+  *context.poison.borrow_mut() = Some(poison);
+  resume(&context.callback_panic);
+  Err(error)
+}
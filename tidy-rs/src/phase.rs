@@ -0,0 +1,298 @@
+//! Typestate API enforcing the order of tidy's processing steps.
+//!
+//! [`Tidy::configure`] starts a pipeline in which every step consumes the current phase and returns the next one, so
+//! only the operations valid at that point can be called: options are set before parsing, the document is repaired
+//! after parsing, and it is saved once it was repaired and optionally diagnosed.
+//!
+//! ```
+//! # use tidy::*;
+//! # fn main() -> Result<(), TidyError> {
+//! let saved = Tidy::configure()?
+//!   .opt(opt::XmlTags, true)?
+//!   .char_encoding("utf8")?
+//!   .parse(b"<test>5 < 6 and 9 > 7</test>")?
+//!   .repair()?
+//!   .diagnose()?
+//!   .force_output_on_error()?
+//!   .save()?;
+//! assert!(saved.output().to_string_lossy().contains("5 &lt; 6"));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The phases only give read access to the diagnostics and, once the document was parsed, to the document tree, as
+//! the [`Tidy`] methods would allow skipping or repeating steps. `into_tidy()` leaves the pipeline for the flat API.
+
+use crate::opt::TidyOpt;
+use crate::{Buffer, Diagnostic, Node, Tidy, TidyConfig, TidyError, TidyOptionId, TidySeverity};
+use std::io::{Read, Write};
+
+macro_rules! phase_common {
+  ($name:ident) => {
+    impl $name {
+      /// Get the messages tidy emitted so far.
+      pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.tidy.diagnostics()
+      }
+
+      /// Leave the pipeline, returning the underlying Tidy instance.
+      pub fn into_tidy(self) -> Tidy {
+        self.tidy
+      }
+    }
+  };
+}
+
+macro_rules! phase_document {
+  ($name:ident) => {
+    impl $name {
+      /// Get the status returned by the last step.
+      pub fn status(&self) -> TidySeverity {
+        self.status.clone()
+      }
+
+      /// Get the root node of the document tree, see [`Tidy::root`].
+      pub fn root(&self) -> Node<'_> {
+        self.tidy.root()
+      }
+
+      /// Get the html element of the document tree, see [`Tidy::html`].
+      pub fn html(&self) -> Option<Node<'_>> {
+        self.tidy.html()
+      }
+
+      /// Get the head element of the document tree, see [`Tidy::head`].
+      pub fn head(&self) -> Option<Node<'_>> {
+        self.tidy.head()
+      }
+
+      /// Get the body element of the document tree, see [`Tidy::body`].
+      pub fn body(&self) -> Option<Node<'_>> {
+        self.tidy.body()
+      }
+    }
+  };
+}
+
+impl Tidy {
+  /// Start a pipeline that enforces the order parse, repair, diagnose and save, see the [`phase`](crate::phase) module.
+  pub fn configure() -> Result<Configuring, TidyError> {
+    Ok(Configuring { tidy: Tidy::new()? })
+  }
+}
+
+/// The configuration phase, options can be set until the document is parsed.
+pub struct Configuring {
+  tidy: Tidy,
+}
+
+phase_common!(Configuring);
+
+impl Configuring {
+  /// Set a typed option, see [`Tidy::opt_set`].
+  pub fn opt<O: TidyOpt>(self, opt: O, val: O::Value) -> Result<Configuring, TidyError> {
+    self.tidy.opt_set(opt, val)?;
+    Ok(self)
+  }
+
+  /// Set an option by name from its config file representation, see [`Tidy::opt_parse_value`].
+  pub fn option(self, optnam: &str, val: &str) -> Result<Configuring, TidyError> {
+    self.tidy.opt_parse_value(optnam, val)?;
+    Ok(self)
+  }
+
+  /// Apply an option snapshot, see [`Tidy::apply_config`].
+  pub fn config(self, config: &TidyConfig) -> Result<Configuring, TidyError> {
+    self.tidy.apply_config(config)?;
+    Ok(self)
+  }
+
+  /// Load options from a config file, see [`Tidy::load_config`].
+  pub fn config_file(self, config_file: &str) -> Result<Configuring, TidyError> {
+    self.tidy.load_config(config_file)?;
+    Ok(self)
+  }
+
+  /// Set the input and output character encoding, see [`Tidy::set_char_encoding`].
+  pub fn char_encoding(self, encnam: &str) -> Result<Configuring, TidyError> {
+    self.tidy.set_char_encoding(encnam)?;
+    Ok(self)
+  }
+
+  /// Register a report filter, see [`Tidy::set_report_filter`].
+  pub fn report_filter<F>(self, filter: F) -> Configuring
+  where
+    F: FnMut(&Diagnostic) -> bool + Send + 'static,
+  {
+    self.tidy.set_report_filter(filter);
+    self
+  }
+
+  /// Parse markup in given byte slice, see [`Tidy::parse_bytes`].
+  pub fn parse(mut self, content: &[u8]) -> Result<Parsed, TidyError> {
+    let status = self.tidy.parse_bytes(content)?;
+    Ok(Parsed {
+      tidy: self.tidy,
+      status,
+    })
+  }
+
+  /// Parse markup in the named file, see [`Tidy::parse_file`].
+  pub fn parse_file(mut self, filename: &str) -> Result<Parsed, TidyError> {
+    let status = self.tidy.parse_file(filename)?;
+    Ok(Parsed {
+      tidy: self.tidy,
+      status,
+    })
+  }
+
+  /// Parse markup from a reader, see [`Tidy::parse_reader`].
+  pub fn parse_reader<R: Read>(mut self, reader: R) -> Result<Parsed, TidyError> {
+    let status = self.tidy.parse_reader(reader)?;
+    Ok(Parsed {
+      tidy: self.tidy,
+      status,
+    })
+  }
+}
+
+/// The document was parsed and can be cleaned and repaired.
+pub struct Parsed {
+  tidy: Tidy,
+  status: TidySeverity,
+}
+
+phase_common!(Parsed);
+phase_document!(Parsed);
+
+impl Parsed {
+  /// Execute configured cleanup and repair operations, see [`Tidy::clean_and_repair`].
+  pub fn repair(mut self) -> Result<Repaired, TidyError> {
+    let status = self.tidy.clean_and_repair()?;
+    Ok(Repaired {
+      tidy: self.tidy,
+      status,
+    })
+  }
+}
+
+/// The document was cleaned and repaired, it can be diagnosed or saved.
+pub struct Repaired {
+  tidy: Tidy,
+  status: TidySeverity,
+}
+
+phase_common!(Repaired);
+phase_document!(Repaired);
+
+impl Repaired {
+  /// Run configured diagnostics, see [`Tidy::run_diagnostics`].
+  pub fn diagnose(self) -> Result<Diagnosed, TidyError> {
+    let status = self.tidy.run_diagnostics()?;
+    Ok(Diagnosed {
+      tidy: self.tidy,
+      status,
+    })
+  }
+
+  /// Save the document to a buffer.
+  pub fn save(self) -> Result<Saved, TidyError> {
+    save(self.tidy)
+  }
+
+  /// Save the document to a writer, see [`Tidy::save_writer`].
+  pub fn save_writer<W: Write>(self, writer: W) -> Result<Saved, TidyError> {
+    save_writer(self.tidy, writer)
+  }
+
+  /// Save the document to the named file, see [`Tidy::save_file`].
+  pub fn save_file(self, filename: &str) -> Result<Saved, TidyError> {
+    save_file(self.tidy, filename)
+  }
+}
+
+/// Diagnostics were run on the repaired document, it can be saved.
+pub struct Diagnosed {
+  tidy: Tidy,
+  status: TidySeverity,
+}
+
+phase_common!(Diagnosed);
+phase_document!(Diagnosed);
+
+impl Diagnosed {
+  /// Write the document even though tidy found errors, which suppress the output otherwise.
+  pub fn force_output_on_error(self) -> Result<Diagnosed, TidyError> {
+    if self.tidy.error_count() > 0 {
+      self
+        .tidy
+        .opt_set_bool(TidyOptionId::TidyForceOutput, true)?;
+    }
+    Ok(self)
+  }
+
+  /// Save the document to a buffer.
+  pub fn save(self) -> Result<Saved, TidyError> {
+    save(self.tidy)
+  }
+
+  /// Save the document to a writer, see [`Tidy::save_writer`].
+  pub fn save_writer<W: Write>(self, writer: W) -> Result<Saved, TidyError> {
+    save_writer(self.tidy, writer)
+  }
+
+  /// Save the document to the named file, see [`Tidy::save_file`].
+  pub fn save_file(self, filename: &str) -> Result<Saved, TidyError> {
+    save_file(self.tidy, filename)
+  }
+}
+
+/// The document was saved, the pipeline is complete.
+pub struct Saved {
+  tidy: Tidy,
+  status: TidySeverity,
+  output: Buffer,
+}
+
+phase_common!(Saved);
+phase_document!(Saved);
+
+impl Saved {
+  /// Get the saved document, empty if it was written to a writer or file.
+  pub fn output(&self) -> &Buffer {
+    &self.output
+  }
+
+  /// Take the saved document.
+  pub fn into_output(self) -> Buffer {
+    self.output
+  }
+}
+
+fn save(mut tidy: Tidy) -> Result<Saved, TidyError> {
+  let status = tidy.save_buffer()?;
+  let output = tidy.take_output();
+  Ok(Saved {
+    tidy,
+    status,
+    output,
+  })
+}
+
+fn save_writer<W: Write>(mut tidy: Tidy, writer: W) -> Result<Saved, TidyError> {
+  let status = tidy.save_writer(writer)?;
+  Ok(Saved {
+    tidy,
+    status,
+    output: Buffer::new(),
+  })
+}
+
+fn save_file(mut tidy: Tidy, filename: &str) -> Result<Saved, TidyError> {
+  let status = tidy.save_file(filename)?;
+  Ok(Saved {
+    tidy,
+    status,
+    output: Buffer::new(),
+  })
+}
@@ -0,0 +1,162 @@
+use crate::{Tidy, TidyError, TidyUtil};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard};
+use tidy_sys::*;
+
+/// A thread-safe pool of Tidy instances sharing the configuration of a template.
+///
+/// Instances are created on demand with the template's option values and returned to the pool when the
+/// [`PooledTidy`] handle is dropped. On return the instance's buffers, diagnostics and callbacks are cleared and
+/// its options are restored to the template configuration via tidyOptCopyConfig() and snapshotted, so the instance
+/// behaves like a fresh one configured like the template, also for [`Tidy::reset`]. Like after reset() the previous
+/// document, its tree and its error and warning counts stay in place until the next parse. Callbacks and the buffers
+/// of the template are not copied.
+///
+/// ```
+/// # use tidy::*;
+/// # use std::sync::Arc;
+/// # use std::thread;
+/// # fn main() -> Result<(), TidyError> {
+/// let template = Tidy::new()?;
+/// template.opt_set_bool(TidyOptionId::TidyXmlTags, true)?;
+/// let pool = Arc::new(TidyPool::new(template));
+///
+/// let workers: Vec<_> = (0..4)
+///   .map(|i| {
+///     let pool = Arc::clone(&pool);
+///     thread::spawn(move || -> Result<String, TidyError> {
+///       let mut tidy = pool.get()?;
+///       tidy.parse_bytes(format!("<worker>{}</worker>", i).as_bytes())?;
+///       tidy.save_buffer()?;
+///       Ok(tidy.output().to_string_lossy())
+///     })
+///   })
+///   .collect();
+/// for (i, worker) in workers.into_iter().enumerate() {
+///   let output = worker.join().unwrap()?;
+///   assert!(output.contains(&format!("<worker>{}</worker>", i)));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct TidyPool {
+  template: Mutex<Tidy>,
+  idle: Mutex<Vec<Tidy>>,
+}
+
+impl TidyPool {
+  /// Create a pool handing out instances configured like the template.
+  pub fn new(template: Tidy) -> TidyPool {
+    TidyPool {
+      template: Mutex::new(template),
+      idle: Mutex::new(Vec::new()),
+    }
+  }
+
+  /// Get an instance from the pool, creating a new one if none is idle.
+  ///
+  /// # Returns
+  /// A handle which returns the instance to the pool when dropped, or a TidyError if a new instance couldn't be
+  /// created or configured.
+  pub fn get(&self) -> Result<PooledTidy<'_>, TidyError> {
+    let idle = lock(&self.idle).pop();
+    let tidy = match idle {
+      Some(tidy) => tidy,
+      None => {
+        let mut tidy = Tidy::new()?;
+        self.restore(&mut tidy)?;
+        tidy
+      }
+    };
+    Ok(PooledTidy {
+      pool: self,
+      tidy: Some(tidy),
+    })
+  }
+
+  /// Get the number of idle instances in the pool.
+  pub fn idle_count(&self) -> usize {
+    lock(&self.idle).len()
+  }
+
+  /// Remove all idle instances from the pool.
+  pub fn clear(&self) {
+    lock(&self.idle).clear();
+  }
+
+  fn restore(&self, tidy: &mut Tidy) -> Result<(), TidyError> {
+    let template = lock(&self.template);
+    unsafe {
+      let mark = tidy.diagnostics_mark();
+      // The copy snapshots the previous options, the template configuration has to become the snapshot for reset().
+      if TidyUtil::tidy_bool_to_bool(tidyOptCopyConfig(tidy.tdoc, template.tdoc))
+        && TidyUtil::tidy_bool_to_bool(tidyOptSnapshot(tidy.tdoc))
+      {
+        tidy.parsed = false;
+        Ok(())
+      } else {
+        Err(TidyError::Config {
+          path: None,
+          status: None,
+          diagnostics: tidy.diagnostics_since(mark),
+        })
+      }
+    }
+  }
+
+  fn release(&self, mut tidy: Tidy) {
+    if tidy.context().poison.borrow().is_some() {
+      return;
+    }
+    tidy.errbuf.clear();
+    tidy.output.clear();
+    tidy.clear_diagnostics();
+    tidy.clear_report_filter();
+    tidy.clear_config_callback();
+    tidy.clear_config_change_callback();
+    // An instance that can't be restored is dropped instead of being handed out with a stale configuration.
+    if self.restore(&mut tidy).is_ok() {
+      tidy.clear_diagnostics();
+      lock(&self.idle).push(tidy);
+    }
+  }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+  mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// A Tidy instance borrowed from a [`TidyPool`], returned to the pool when dropped.
+pub struct PooledTidy<'pool> {
+  pool: &'pool TidyPool,
+  tidy: Option<Tidy>,
+}
+
+impl<'pool> PooledTidy<'pool> {
+  /// Take the instance out of the pool for good.
+  pub fn detach(mut self) -> Tidy {
+    self.tidy.take().unwrap()
+  }
+}
+
+impl<'pool> Deref for PooledTidy<'pool> {
+  type Target = Tidy;
+
+  fn deref(&self) -> &Tidy {
+    self.tidy.as_ref().unwrap()
+  }
+}
+
+impl<'pool> DerefMut for PooledTidy<'pool> {
+  fn deref_mut(&mut self) -> &mut Tidy {
+    self.tidy.as_mut().unwrap()
+  }
+}
+
+impl<'pool> Drop for PooledTidy<'pool> {
+  fn drop(&mut self) {
+    if let Some(tidy) = self.tidy.take() {
+      self.pool.release(tidy);
+    }
+  }
+}
@@ -0,0 +1,258 @@
+//! Allowlist based sanitation of the repaired document tree.
+//!
+//! [`Tidy::sanitize`] walks the tree after clean and repair and discards every element and attribute not in an
+//! [`Allowlist`], so user-submitted markup is repaired and sanitized in a single pass before it's saved. Event
+//! handler attributes and URLs with a scheme other than http, https and mailto are discarded even if the allowlist
+//! contains their attribute.
+//!
+//! ```
+//! # use tidy::*;
+//! # use tidy::sanitize::{sanitize_html, Allowlist};
+//! # fn main() -> Result<(), TidyError> {
+//! let config: TidyConfig = vec![("show-body-only", "yes")].into_iter().collect();
+//! let input = "<p onclick='steal()'>Hi <a href='javascript:steal()'>there</a><script>steal()</script>";
+//! let cleaned = sanitize_html(input, &Allowlist::default(), &config)?;
+//! let output = cleaned.output_string();
+//! assert!(output.contains("<p>Hi <a>there</a></p>"));
+//! assert!(!output.contains("steal"));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::clean::{self, Cleaned};
+use crate::{Attr, Node, Tidy, TidyAttrId, TidyConfig, TidyError, TidyNodeType, TidyTagId};
+use std::collections::HashSet;
+
+/// The elements and attributes kept by [`Tidy::sanitize`].
+///
+/// Elements are matched by their TidyTagId, so elements tidy doesn't know are always discarded, as are comments,
+/// processing instructions and server side code. Text, the doctype and the html, head, body and title elements
+/// making up the document structure are always kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Allowlist {
+  tags: HashSet<TidyTagId>,
+  attrs: HashSet<TidyAttrId>,
+}
+
+impl Allowlist {
+  /// Create an allowlist without any elements or attributes.
+  pub fn new() -> Allowlist {
+    Allowlist {
+      tags: HashSet::new(),
+      attrs: HashSet::new(),
+    }
+  }
+
+  /// Allow given element.
+  pub fn tag(mut self, tag: TidyTagId) -> Allowlist {
+    self.tags.insert(tag);
+    self
+  }
+
+  /// Allow given elements.
+  pub fn tags<I: IntoIterator<Item = TidyTagId>>(mut self, tags: I) -> Allowlist {
+    self.tags.extend(tags);
+    self
+  }
+
+  /// Allow given attribute on all allowed elements.
+  pub fn attr(mut self, attr: TidyAttrId) -> Allowlist {
+    self.attrs.insert(attr);
+    self
+  }
+
+  /// Allow given attributes on all allowed elements.
+  pub fn attrs<I: IntoIterator<Item = TidyAttrId>>(mut self, attrs: I) -> Allowlist {
+    self.attrs.extend(attrs);
+    self
+  }
+
+  /// Indicates whether or not the node is kept.
+  pub fn allows_node(&self, node: Node<'_>) -> bool {
+    match node.node_type() {
+      TidyNodeType::TidyNode_Root
+      | TidyNodeType::TidyNode_DocType
+      | TidyNodeType::TidyNode_Text => true,
+      TidyNodeType::TidyNode_Start | TidyNodeType::TidyNode_StartEnd => match node.tag_id() {
+        TidyTagId::TidyTag_HTML
+        | TidyTagId::TidyTag_HEAD
+        | TidyTagId::TidyTag_BODY
+        | TidyTagId::TidyTag_TITLE => true,
+        TidyTagId::TidyTag_UNKNOWN => false,
+        tag => self.tags.contains(&tag),
+      },
+      _ => false,
+    }
+  }
+
+  /// Indicates whether or not the attribute is kept.
+  pub fn allows_attr(&self, attr: Attr<'_>) -> bool {
+    self.attrs.contains(&attr.id())
+      && !attr.is_event()
+      && is_safe_value(attr.id(), attr.value_bytes())
+  }
+}
+
+/// Formatting, list, table, link and image elements with the attributes needed to display them.
+impl Default for Allowlist {
+  fn default() -> Allowlist {
+    use tidy_sys::TidyAttrId::*;
+    use tidy_sys::TidyTagId::*;
+    Allowlist::new()
+      .tags(vec![
+        TidyTag_A,
+        TidyTag_ABBR,
+        TidyTag_B,
+        TidyTag_BLOCKQUOTE,
+        TidyTag_BR,
+        TidyTag_CAPTION,
+        TidyTag_CITE,
+        TidyTag_CODE,
+        TidyTag_DD,
+        TidyTag_DIV,
+        TidyTag_DL,
+        TidyTag_DT,
+        TidyTag_EM,
+        TidyTag_H1,
+        TidyTag_H2,
+        TidyTag_H3,
+        TidyTag_H4,
+        TidyTag_H5,
+        TidyTag_H6,
+        TidyTag_HR,
+        TidyTag_I,
+        TidyTag_IMG,
+        TidyTag_LI,
+        TidyTag_OL,
+        TidyTag_P,
+        TidyTag_PRE,
+        TidyTag_Q,
+        TidyTag_S,
+        TidyTag_SMALL,
+        TidyTag_SPAN,
+        TidyTag_STRONG,
+        TidyTag_SUB,
+        TidyTag_SUP,
+        TidyTag_TABLE,
+        TidyTag_TBODY,
+        TidyTag_TD,
+        TidyTag_TFOOT,
+        TidyTag_TH,
+        TidyTag_THEAD,
+        TidyTag_TR,
+        TidyTag_U,
+        TidyTag_UL,
+      ])
+      .attrs(vec![
+        TidyAttr_ALT,
+        TidyAttr_CITE,
+        TidyAttr_CLASS,
+        TidyAttr_COLSPAN,
+        TidyAttr_DIR,
+        TidyAttr_HEIGHT,
+        TidyAttr_HREF,
+        TidyAttr_LANG,
+        TidyAttr_ROWSPAN,
+        TidyAttr_SRC,
+        TidyAttr_TITLE,
+        TidyAttr_WIDTH,
+      ])
+  }
+}
+
+// Attributes holding URLs, srcset holds a comma separated list of URLs followed by their descriptors.
+fn is_safe_value(id: TidyAttrId, value: Option<Vec<u8>>) -> bool {
+  use tidy_sys::TidyAttrId::*;
+  let value = match value {
+    Some(value) => value,
+    None => return true,
+  };
+  match id {
+    TidyAttr_SRCSET => value.split(|&b| b == b',').all(|candidate| {
+      let url = candidate
+        .split(u8::is_ascii_whitespace)
+        .find(|url| !url.is_empty())
+        .unwrap_or_default();
+      is_safe_url(url)
+    }),
+    TidyAttr_ACTION | TidyAttr_ARCHIVE | TidyAttr_BACKGROUND | TidyAttr_CITE | TidyAttr_CLASSID
+    | TidyAttr_CODEBASE | TidyAttr_DATA | TidyAttr_FORMACTION | TidyAttr_HREF | TidyAttr_ICON
+    | TidyAttr_LONGDESC | TidyAttr_LOWSRC | TidyAttr_MANIFEST | TidyAttr_POSTER
+    | TidyAttr_PROFILE | TidyAttr_SRC | TidyAttr_USEMAP => is_safe_url(&value),
+    _ => true,
+  }
+}
+
+// Only relative URLs and the http, https and mailto schemes are safe. Browsers ignore whitespace and control
+// characters in the scheme, and a character reference can hide the scheme or the colon ending it, so an ampersand
+// anywhere before the path, query or fragment makes the URL unsafe as well.
+fn is_safe_url(url: &[u8]) -> bool {
+  let end = url
+    .iter()
+    .position(|b| b"/?#".contains(b))
+    .unwrap_or(url.len());
+  let prefix = &url[..end];
+  if prefix.contains(&b'&') {
+    return false;
+  }
+  let colon = match prefix.iter().position(|&b| b == b':') {
+    Some(colon) => colon,
+    None => return true,
+  };
+  let scheme: Vec<u8> = prefix[..colon]
+    .iter()
+    .filter(|b| !b.is_ascii_whitespace() && !b.is_ascii_control())
+    .map(u8::to_ascii_lowercase)
+    .collect();
+  [&b"http"[..], b"https", b"mailto"].contains(&&scheme[..])
+}
+
+impl Tidy {
+  /// Discard all elements and attributes not allowed by the allowlist, see the [`sanitize`](crate::sanitize) module.
+  ///
+  /// Call after clean_and_repair, as the repair may add elements and attributes.
+  ///
+  /// # Returns
+  /// The number of nodes and attributes discarded, not counting the children of discarded nodes.
+  pub fn sanitize(&mut self, allowlist: &Allowlist) -> usize {
+    self.discard_nodes(|node| !allowlist.allows_node(node))
+      + self.discard_attrs(|attr| !allowlist.allows_attr(attr))
+  }
+}
+
+/// Parse, clean, repair and sanitize an HTML document in one go.
+///
+/// Like [`clean_html`](crate::clean_html) forcing the output, sanitizing the repaired document before diagnostics are
+/// run and the output is saved. The preserve-entities option is disabled and quote-ampersand enabled regardless of the
+/// config, so character references are always written in their canonical form.
+///
+/// # Returns
+/// The sanitized document together with the diagnostics and the final status, or a TidyError if a step failed.
+///
+/// ```
+/// # use tidy::*;
+/// # use tidy::sanitize::{sanitize_html, Allowlist};
+/// # fn main() -> Result<(), TidyError> {
+/// let config: TidyConfig = vec![("show-body-only", "yes"), ("wrap", "0")].into_iter().collect();
+/// let input = "<a href='vbscript:msgbox(1)'>vb</a> <a href='data:text/html,<script>alert(1)</script>'>data</a> \
+///   <img src='DATA:image/svg+xml;base64,PHN2Zy8+' alt='img'> <a href='https://example.com/?q=a:b'>web</a>";
+/// let output = sanitize_html(input, &Allowlist::default(), &config)?.output_string();
+/// assert!(output.contains("<a>vb</a> <a>data</a> <img alt=\"img\">"));
+/// assert!(output.contains("<a href=\"https://example.com/?q=a:b\">web</a>"));
+/// # Ok(())
+/// # }
+/// ```
+pub fn sanitize_html(
+  html: &str,
+  allowlist: &Allowlist,
+  config: &TidyConfig,
+) -> Result<Cleaned, TidyError> {
+  clean::run(
+    html.as_bytes(),
+    config,
+    false,
+    Some("utf8"),
+    true,
+    Some(allowlist),
+  )
+}
@@ -0,0 +1,220 @@
+use crate::TidyContext;
+use libc::*;
+use tidy_sys::*;
+
+/// Position of a node in the input document and the line it was written to in the output.
+///
+/// Lines and columns are 1-based as in diagnostics. Tidy's pretty printer only reports the output line, so output
+/// positions are mapped at line granularity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceMapping {
+  /// Line of the node in the input.
+  pub src_line: c_uint,
+  /// Column of the node in the input.
+  pub src_col: c_uint,
+  /// Line the node was written to in the output.
+  pub dst_line: c_uint,
+}
+
+/// Mappings from the input to the output of a save, see
+/// [`Tidy::save_buffer_with_source_map`](crate::Tidy::save_buffer_with_source_map).
+///
+/// ```
+/// # use tidy::*;
+/// # fn main() -> Result<(), TidyError> {
+/// let mut tidy = Tidy::new()?;
+/// tidy.parse_bytes(b"<title>T</title><p>one <b>two</b></p>\n<p>three</p>")?;
+/// tidy.clean_and_repair()?;
+/// let (_, map) = tidy.save_buffer_with_source_map()?;
+///
+/// let dst_line = map.dst_line(2, 1).unwrap();
+/// let output = tidy.output().to_string_lossy();
+/// assert!(output.lines().nth(dst_line as usize - 1).unwrap().contains("three"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+  mappings: Vec<SourceMapping>,
+}
+
+impl SourceMap {
+  pub(crate) fn new(mut mappings: Vec<SourceMapping>) -> SourceMap {
+    mappings.dedup();
+    SourceMap { mappings }
+  }
+
+  /// Get the mappings in the order the output was written.
+  pub fn mappings(&self) -> &[SourceMapping] {
+    &self.mappings
+  }
+
+  /// Indicates whether or not the map is empty.
+  pub fn is_empty(&self) -> bool {
+    self.mappings.is_empty()
+  }
+
+  /// Get the output line an input position ended up on.
+  ///
+  /// # Returns
+  /// The output line of the node starting closest before the position, None if no node starts before it.
+  pub fn dst_line(&self, src_line: c_uint, src_col: c_uint) -> Option<c_uint> {
+    self
+      .mappings
+      .iter()
+      .filter(|m| (m.src_line, m.src_col) <= (src_line, src_col))
+      .max_by_key(|m| (m.src_line, m.src_col))
+      .map(|m| m.dst_line)
+  }
+
+  /// Get the input position an output line was written from.
+  ///
+  /// # Returns
+  /// The input line and column of the first node written to the output line, None if no node was written to it.
+  pub fn src_position(&self, dst_line: c_uint) -> Option<(c_uint, c_uint)> {
+    self
+      .mappings
+      .iter()
+      .find(|m| m.dst_line == dst_line)
+      .map(|m| (m.src_line, m.src_col))
+  }
+
+  /// Export the map as a Source Map revision 3 JSON document.
+  ///
+  /// Each output line maps to the input position of the first node written to it, at column 0 of the output line.
+  ///
+  /// # Parameters
+  /// **file**	The name of the output file.
+  /// **source**	The name of the input file.
+  /// **source_content**	The input document to embed, if any.
+  ///
+  /// ```
+  /// # use tidy::*;
+  /// # fn main() -> Result<(), TidyError> {
+  /// let config = TidyConfig::new();
+  /// let cleaned = clean_html("<title>T</title>\n<p>paragraph", &config, true)?;
+  /// let json = cleaned.source_map.to_json("out.html", "in.html", None);
+  /// assert!(json.starts_with(r#"{"version":3,"file":"out.html","sources":["in.html"]"#));
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn to_json(&self, file: &str, source: &str, source_content: Option<&str>) -> String {
+    let mut json = String::from("{\"version\":3,\"file\":");
+    push_json_string(&mut json, file);
+    json.push_str(",\"sources\":[");
+    push_json_string(&mut json, source);
+    json.push(']');
+    if let Some(content) = source_content {
+      json.push_str(",\"sourcesContent\":[");
+      push_json_string(&mut json, content);
+      json.push(']');
+    }
+    json.push_str(",\"names\":[],\"mappings\":\"");
+    json.push_str(&self.vlq_mappings());
+    json.push_str("\"}");
+    json
+  }
+
+  fn vlq_mappings(&self) -> String {
+    let last_line = match self.mappings.iter().map(|m| m.dst_line).max() {
+      Some(line) => line as usize,
+      None => return String::new(),
+    };
+    // The first mapping written to each output line, indexed by the 0-based output line.
+    let mut lines = vec![None; last_line];
+    for m in &self.mappings {
+      if let Some(line @ None) = (m.dst_line as usize)
+        .checked_sub(1)
+        .and_then(|i| lines.get_mut(i))
+      {
+        *line = Some((m.src_line, m.src_col));
+      }
+    }
+    let mut out = String::new();
+    let (mut prev_line, mut prev_col) = (0i64, 0i64);
+    for (i, line) in lines.into_iter().enumerate() {
+      if i > 0 {
+        out.push(';');
+      }
+      if let Some((src_line, src_col)) = line {
+        // Source map positions are 0-based, the source index is always 0.
+        let line = i64::from(src_line.saturating_sub(1));
+        let col = i64::from(src_col.saturating_sub(1));
+        push_vlq(&mut out, 0);
+        push_vlq(&mut out, 0);
+        push_vlq(&mut out, line - prev_line);
+        push_vlq(&mut out, col - prev_col);
+        prev_line = line;
+        prev_col = col;
+      }
+    }
+    out
+  }
+}
+
+fn push_vlq(out: &mut String, value: i64) {
+  const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+  let mut vlq = if value < 0 {
+    ((-value as u64) << 1) | 1
+  } else {
+    (value as u64) << 1
+  };
+  loop {
+    let mut digit = (vlq & 0x1f) as usize;
+    vlq >>= 5;
+    if vlq > 0 {
+      digit |= 0x20;
+    }
+    out.push(BASE64[digit] as char);
+    if vlq == 0 {
+      break;
+    }
+  }
+}
+
+fn push_json_string(out: &mut String, value: &str) {
+  out.push('"');
+  for c in value.chars() {
+    match c {
+      '"' => out.push_str("\\\""),
+      '\\' => out.push_str("\\\\"),
+      '\n' => out.push_str("\\n"),
+      '\r' => out.push_str("\\r"),
+      '\t' => out.push_str("\\t"),
+      c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+      c => out.push(c),
+    }
+  }
+  out.push('"');
+}
+
+impl<'a> IntoIterator for &'a SourceMap {
+  type Item = &'a SourceMapping;
+  type IntoIter = std::slice::Iter<'a, SourceMapping>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.mappings.iter()
+  }
+}
+
+pub(crate) unsafe extern "C" fn pretty_printer_callback(
+  tdoc: TidyDoc,
+  line: uint,
+  col: uint,
+  dest_line: uint,
+) {
+  let context = tidyGetAppData(tdoc) as *const TidyContext;
+  if context.is_null() {
+    return;
+  }
+  if let Ok(mut source_map) = (*context).source_map.try_borrow_mut() {
+    if let Some(mappings) = source_map.as_mut() {
+      mappings.push(SourceMapping {
+        src_line: line,
+        src_col: col,
+        dst_line: dest_line,
+      });
+    }
+  }
+}
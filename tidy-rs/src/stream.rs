@@ -0,0 +1,198 @@
+use crate::panic::Payload;
+use crate::TidyUtil;
+use libc::*;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use tidy_sys::*;
+
+const CHUNK_SIZE: usize = 8192;
+
+/// Adapts a reader to the TidyInputSource callbacks.
+///
+/// Input is read in chunks, bytes handed back by tidy are kept on a pushback stack. The first read error ends the input
+/// and is kept until the caller takes it after parsing. A panic of the reader ends the input as well, it is resumed by
+/// the caller once tidy returned.
+pub(crate) struct ReaderSource<R: Read> {
+  reader: R,
+  buf: Vec<u8>,
+  pos: usize,
+  pushback: Vec<u8>,
+  done: bool,
+  error: Option<io::Error>,
+  panic: Option<Payload>,
+}
+
+impl<R: Read> ReaderSource<R> {
+  pub(crate) fn new(reader: R) -> ReaderSource<R> {
+    ReaderSource {
+      reader,
+      buf: Vec::new(),
+      pos: 0,
+      pushback: Vec::new(),
+      done: false,
+      error: None,
+      panic: None,
+    }
+  }
+
+  /// Initialize a TidyInputSource reading from this adapter, which must not move while the source is in use.
+  pub(crate) unsafe fn init(&mut self, source: *mut TidyInputSource) -> bool {
+    TidyUtil::tidy_bool_to_bool(tidyInitSource(
+      source,
+      self as *mut ReaderSource<R> as *mut c_void,
+      Some(get_byte::<R>),
+      Some(unget_byte::<R>),
+      Some(is_eof::<R>),
+    ))
+  }
+
+  pub(crate) fn take_error(&mut self) -> Option<io::Error> {
+    self.error.take()
+  }
+
+  pub(crate) fn take_panic(&mut self) -> Option<Payload> {
+    self.panic.take()
+  }
+
+  /// Make sure there is a byte available, returns false at the end of input.
+  fn fill(&mut self) -> bool {
+    if !self.pushback.is_empty() || self.pos < self.buf.len() {
+      return true;
+    }
+    if self.done {
+      return false;
+    }
+    self.buf.resize(CHUNK_SIZE, 0);
+    self.pos = 0;
+    loop {
+      let (reader, buf) = (&mut self.reader, &mut self.buf);
+      let read = match catch_unwind(AssertUnwindSafe(|| reader.read(buf))) {
+        Ok(read) => read,
+        Err(payload) => {
+          self.buf.clear();
+          self.done = true;
+          self.panic = Some(payload);
+          return false;
+        }
+      };
+      match read {
+        Ok(0) => {
+          self.buf.clear();
+          self.done = true;
+          return false;
+        }
+        Ok(n) => {
+          self.buf.truncate(n);
+          return true;
+        }
+        Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+        Err(e) => {
+          self.buf.clear();
+          self.done = true;
+          self.error = Some(e);
+          return false;
+        }
+      }
+    }
+  }
+
+  fn next_byte(&mut self) -> Option<u8> {
+    if !self.fill() {
+      return None;
+    }
+    match self.pushback.pop() {
+      Some(bt) => Some(bt),
+      None => {
+        self.pos += 1;
+        Some(self.buf[self.pos - 1])
+      }
+    }
+  }
+}
+
+unsafe extern "C" fn get_byte<R: Read>(data: *mut c_void) -> c_int {
+  let source = &mut *(data as *mut ReaderSource<R>);
+  match source.next_byte() {
+    Some(bt) => bt as c_int,
+    // Cast to uint by tidy, this is its EndOfStream marker.
+    None => -1,
+  }
+}
+
+unsafe extern "C" fn unget_byte<R: Read>(data: *mut c_void, bt: byte) {
+  let source = &mut *(data as *mut ReaderSource<R>);
+  source.pushback.push(bt);
+}
+
+unsafe extern "C" fn is_eof<R: Read>(data: *mut c_void) -> Bool {
+  let source = &mut *(data as *mut ReaderSource<R>);
+  TidyUtil::bool_to_tidy_bool(!source.fill())
+}
+
+/// Adapts a writer to the TidyOutputSink callbacks.
+///
+/// Output is collected in chunks before it is written. The first write error drops all further output and is kept
+/// until the caller takes it after saving. A panic of the writer drops all further output as well, it is resumed by
+/// finish().
+pub(crate) struct WriterSink<W: Write> {
+  writer: W,
+  buf: Vec<u8>,
+  error: Option<io::Error>,
+  panic: Option<Payload>,
+}
+
+impl<W: Write> WriterSink<W> {
+  pub(crate) fn new(writer: W) -> WriterSink<W> {
+    WriterSink {
+      writer,
+      buf: Vec::with_capacity(CHUNK_SIZE),
+      error: None,
+      panic: None,
+    }
+  }
+
+  /// Initialize a TidyOutputSink writing to this adapter, which must not move while the sink is in use.
+  pub(crate) unsafe fn init(&mut self, sink: *mut TidyOutputSink) -> bool {
+    TidyUtil::tidy_bool_to_bool(tidyInitSink(
+      sink,
+      self as *mut WriterSink<W> as *mut c_void,
+      Some(put_byte::<W>),
+    ))
+  }
+
+  /// Write out the remaining output and flush the writer.
+  pub(crate) fn finish(&mut self) -> io::Result<()> {
+    if let Some(payload) = self.panic.take() {
+      resume_unwind(payload);
+    }
+    if let Some(error) = self.error.take() {
+      return Err(error);
+    }
+    self.writer.write_all(&self.buf)?;
+    self.buf.clear();
+    self.writer.flush()
+  }
+
+  fn push(&mut self, bt: u8) {
+    if self.error.is_some() || self.panic.is_some() {
+      return;
+    }
+    self.buf.push(bt);
+    if self.buf.len() >= CHUNK_SIZE {
+      let (writer, buf) = (&mut self.writer, &self.buf);
+      match catch_unwind(AssertUnwindSafe(|| writer.write_all(buf))) {
+        Ok(Ok(())) => (),
+        Ok(Err(error)) => self.error = Some(error),
+        Err(payload) => self.panic = Some(payload),
+      }
+      self.buf.clear();
+    }
+  }
+}
+
+unsafe extern "C" fn put_byte<W: Write>(data: *mut c_void, bt: byte) {
+  let sink = &mut *(data as *mut WriterSink<W>);
+  sink.push(bt);
+}
@@ -88,6 +88,8 @@ fn main() -> Result<(), Box<dyn Error>> {
         .unwrap()))
         .rustified_enum("^Tidy.*")
         .allowlist_function("^tidy.*")
+        .allowlist_function("^getInstalledLanguageList$")
+        .allowlist_function("^getNextInstalledLanguage$")
         .allowlist_var("^tidy.*")
         .layout_tests(false)
         .parse_callbacks(Box::new(bindgen::CargoCallbacks))
@@ -113,6 +115,16 @@ fn main() -> Result<(), Box<dyn Error>> {
         pub name: ctmbstr,
     }";
     let replaced = re.replace(&contents, new_val);
+
+    // libtidy reports out of memory through the allocator's and the global panic callback, which the Rust side may
+    // handle by unwinding back to the caller of the libtidy function. Unwinding through an import or callback is only
+    // defined with the C-unwind ABI.
+    let replaced = replaced.replace("extern \"C\" {", "extern \"C-unwind\" {");
+    let unwind = |caps: &regex::Captures| caps[0].replace("extern \"C\" fn", "extern \"C-unwind\" fn");
+    let re = Regex::new(r"(?s)pub struct _TidyAllocatorVtbl \{.+?\n\}").unwrap();
+    let replaced = re.replace(&replaced, unwind);
+    let re = Regex::new(r"(?s)pub type Tidy(?:Malloc|Realloc|Free|Panic) =.+?;").unwrap();
+    let replaced = re.replace_all(&replaced, unwind);
     let mut file_w = OpenOptions::new().write(true).truncate(true).open(out_fn)?;
     file_w.write(replaced.as_bytes())?;
     drop(file_w);
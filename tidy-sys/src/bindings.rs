@@ -1622,54 +1622,54 @@ pub struct _TidyAllocator {
 #[derive(Debug, Copy, Clone)]
 pub struct _TidyAllocatorVtbl {
     pub alloc: ::std::option::Option<
-        unsafe extern "C" fn(
+        unsafe extern "C-unwind" fn(
             self_: *mut TidyAllocator,
             nBytes: size_t,
         ) -> *mut ::std::os::raw::c_void,
     >,
     pub realloc: ::std::option::Option<
-        unsafe extern "C" fn(
+        unsafe extern "C-unwind" fn(
             self_: *mut TidyAllocator,
             block: *mut ::std::os::raw::c_void,
             nBytes: size_t,
         ) -> *mut ::std::os::raw::c_void,
     >,
     pub free: ::std::option::Option<
-        unsafe extern "C" fn(self_: *mut TidyAllocator, block: *mut ::std::os::raw::c_void),
+        unsafe extern "C-unwind" fn(self_: *mut TidyAllocator, block: *mut ::std::os::raw::c_void),
     >,
-    pub panic: ::std::option::Option<unsafe extern "C" fn(self_: *mut TidyAllocator, msg: ctmbstr)>,
+    pub panic: ::std::option::Option<unsafe extern "C-unwind" fn(self_: *mut TidyAllocator, msg: ctmbstr)>,
 }
 #[doc = " Callback for `malloc` replacement"]
 pub type TidyMalloc =
-    ::std::option::Option<unsafe extern "C" fn(len: size_t) -> *mut ::std::os::raw::c_void>;
+    ::std::option::Option<unsafe extern "C-unwind" fn(len: size_t) -> *mut ::std::os::raw::c_void>;
 #[doc = " Callback for `realloc` replacement"]
 pub type TidyRealloc = ::std::option::Option<
-    unsafe extern "C" fn(
+    unsafe extern "C-unwind" fn(
         buf: *mut ::std::os::raw::c_void,
         len: size_t,
     ) -> *mut ::std::os::raw::c_void,
 >;
 #[doc = " Callback for `free` replacement"]
-pub type TidyFree = ::std::option::Option<unsafe extern "C" fn(buf: *mut ::std::os::raw::c_void)>;
+pub type TidyFree = ::std::option::Option<unsafe extern "C-unwind" fn(buf: *mut ::std::os::raw::c_void)>;
 #[doc = " Callback for out of memory panic state"]
-pub type TidyPanic = ::std::option::Option<unsafe extern "C" fn(mssg: ctmbstr)>;
-extern "C" {
+pub type TidyPanic = ::std::option::Option<unsafe extern "C-unwind" fn(mssg: ctmbstr)>;
+extern "C-unwind" {
     #[doc = " Give Tidy a `malloc()` replacement"]
     pub fn tidySetMallocCall(fmalloc: TidyMalloc) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Give Tidy a `realloc()` replacement"]
     pub fn tidySetReallocCall(frealloc: TidyRealloc) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Give Tidy a `free()` replacement"]
     pub fn tidySetFreeCall(ffree: TidyFree) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Give Tidy an \"out of memory\" handler"]
     pub fn tidySetPanicCall(fpanic: TidyPanic) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " The primary creation of a document instance. Instances of a TidyDoc are used"]
     #[doc = " throughout the API as a token to represent a particular document. You must"]
     #[doc = " create at least one TidyDoc instance to initialize the library and begin"]
@@ -1678,7 +1678,7 @@ extern "C" {
     #[doc = " @result Returns a TidyDoc instance."]
     pub fn tidyCreate() -> TidyDoc;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Create a document supplying your own, custom TidyAllocator instead of using"]
     #[doc = " the built-in default. See the @ref Memory module if you want to create and"]
     #[doc = " use your own allocator."]
@@ -1686,40 +1686,40 @@ extern "C" {
     #[doc = " @result Returns a TidyDoc instance."]
     pub fn tidyCreateWithAllocator(allocator: *mut TidyAllocator) -> TidyDoc;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Free all memory and release the TidyDoc. The TidyDoc can not be used after"]
     #[doc = " this call."]
     #[doc = " @param tdoc The TidyDoc to free."]
     pub fn tidyRelease(tdoc: TidyDoc);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Allows the host application to store a chunk of data with each TidyDoc"]
     #[doc = " instance. This can be useful for callbacks, such as saving a reference to"]
     #[doc = " `self` within the document."]
     pub fn tidySetAppData(tdoc: TidyDoc, appData: *mut ::std::os::raw::c_void);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Returns the data previously stored with `tidySetAppData()`."]
     #[doc = " @param tdoc  document where data has been stored."]
     #[doc = " @result The pointer to the data block previously stored."]
     pub fn tidyGetAppData(tdoc: TidyDoc) -> *mut ::std::os::raw::c_void;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the release date for the current library."]
     #[doc = " @result The string representing the release date."]
     pub fn tidyReleaseDate() -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the version number for the current library."]
     #[doc = " @result The string representing the version number."]
     pub fn tidyLibraryVersion() -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the platform for which Tidy was built."]
     #[doc = " @result The string representing the version number."]
     pub fn tidyPlatform() -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get status of current document."]
     #[doc = " @param tdoc An instance of a TidyDoc to query."]
     #[doc = " @result Returns the highest of `2` indicating that errors were present in"]
@@ -1727,20 +1727,20 @@ extern "C" {
     #[doc = "         everything being okay."]
     pub fn tidyStatus(tdoc: TidyDoc) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Gets the version of HTML that was output, as an integer, times 100. For"]
     #[doc = " example, HTML5 will return 500; HTML4.0.1 will return 401."]
     #[doc = " @param tdoc An instance of a TidyDoc to query."]
     #[doc = " @result Returns the HTML version number (x100)."]
     pub fn tidyDetectedHtmlVersion(tdoc: TidyDoc) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Indicates whether the output document is or isn't XHTML."]
     #[doc = " @param tdoc An instance of a TidyDoc to query."]
     #[doc = " @result Returns `yes` if the document is an XHTML type."]
     pub fn tidyDetectedXhtml(tdoc: TidyDoc) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Indicates whether or not the input document was XML. If TidyXml tags is"]
     #[doc = " true, or there was an XML declaration in the input document, then this"]
     #[doc = " function will return yes."]
@@ -1748,49 +1748,49 @@ extern "C" {
     #[doc = " @result Returns `yes` if the input document was XML."]
     pub fn tidyDetectedGenericXml(tdoc: TidyDoc) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Indicates the number of TidyError messages that were generated. For any"]
     #[doc = " value greater than `0`, output is suppressed unless TidyForceOutput is set."]
     #[doc = " @param tdoc An instance of a TidyDoc to query."]
     #[doc = " @result Returns the number of TidyError messages that were generated."]
     pub fn tidyErrorCount(tdoc: TidyDoc) -> uint;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Indicates the number of TidyWarning messages that were generated."]
     #[doc = " @param tdoc An instance of a TidyDoc to query."]
     #[doc = " @result Returns the number of TidyWarning messages that were generated."]
     pub fn tidyWarningCount(tdoc: TidyDoc) -> uint;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Indicates the number of TidyAccess messages that were generated."]
     #[doc = " @param tdoc An instance of a TidyDoc to query."]
     #[doc = " @result Returns the number of TidyAccess messages that were generated."]
     pub fn tidyAccessWarningCount(tdoc: TidyDoc) -> uint;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Indicates the number of configuration error messages that were generated."]
     #[doc = " @param tdoc An instance of a TidyDoc to query."]
     #[doc = " @result Returns the number of configuration error messages that were"]
     #[doc = "         generated."]
     pub fn tidyConfigErrorCount(tdoc: TidyDoc) -> uint;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Write more complete information about errors to current error sink."]
     #[doc = " @param tdoc An instance of a TidyDoc to query."]
     pub fn tidyErrorSummary(tdoc: TidyDoc);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Write more general information about markup to current error sink."]
     #[doc = " @param tdoc An instance of a TidyDoc to query."]
     pub fn tidyGeneralInfo(tdoc: TidyDoc);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Load an ASCII Tidy configuration file and set the configuration per its"]
     #[doc = " contents. Reports config option errors, which can be filtered."]
     #[doc = " @result Returns 0 upon success, or any other value if there was an option error."]
     pub fn tidyLoadConfig(tdoc: TidyDoc, configFile: ctmbstr) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Load a Tidy configuration file with the specified character encoding, and"]
     #[doc = " set the configuration per its contents.  Reports config option errors, which can be filtered."]
     #[doc = " @result Returns 0 upon success, or any other value if there was an option error."]
@@ -1800,13 +1800,13 @@ extern "C" {
         charenc: ctmbstr,
     ) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Determine whether or not a particular file exists. On Unix systems, the use"]
     #[doc = " of the tilde to represent the user's home directory is supported."]
     #[doc = " @result Returns `yes` or `no`, indicating whether or not the file exists."]
     pub fn tidyFileExists(tdoc: TidyDoc, filename: ctmbstr) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Set the input/output character encoding for parsing markup. Valid values"]
     #[doc = " include `ascii`, `latin1`, `raw`, `utf8`, `iso2022`, `mac`, `win1252`,"]
     #[doc = " `utf16le`, `utf16be`, `utf16`, `big5`, and `shiftjis`. These values are not"]
@@ -1816,14 +1816,14 @@ extern "C" {
     #[doc = " @result Returns 0 upon success, or a system standard error number `EINVAL`."]
     pub fn tidySetCharEncoding(tdoc: TidyDoc, encnam: ctmbstr) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Set the input encoding for parsing markup.  Valid values include `ascii`,"]
     #[doc = " `latin1`, `raw`, `utf8`, `iso2022`, `mac`, `win1252`, `utf16le`, `utf16be`,"]
     #[doc = " `utf16`, `big5`, and `shiftjis`. These values are not case sensitive."]
     #[doc = " @result Returns 0 upon success, or a system standard error number `EINVAL`."]
     pub fn tidySetInCharEncoding(tdoc: TidyDoc, encnam: ctmbstr) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Set the input encoding for writing markup.  Valid values include `ascii`,"]
     #[doc = " `latin1`, `raw`, `utf8`, `iso2022`, `mac`, `win1252`, `utf16le`, `utf16be`,"]
     #[doc = " `utf16`, `big5`, and `shiftjis`. These values are not case sensitive."]
@@ -1842,7 +1842,7 @@ extern "C" {
 #[doc = "         an unknown configuration option error."]
 pub type TidyOptCallback =
     ::std::option::Option<unsafe extern "C" fn(option: ctmbstr, value: ctmbstr) -> Bool>;
-extern "C" {
+extern "C-unwind" {
     #[doc = " Applications using TidyLib may want to augment command-line and"]
     #[doc = " configuration file options. Setting this callback allows a LibTidy"]
     #[doc = " application developer to examine command-line and configuration file options"]
@@ -1864,7 +1864,7 @@ extern "C" {
 pub type TidyConfigCallback = ::std::option::Option<
     unsafe extern "C" fn(tdoc: TidyDoc, option: ctmbstr, value: ctmbstr) -> Bool,
 >;
-extern "C" {
+extern "C-unwind" {
     #[doc = " Applications using TidyLib may want to augment command-line and"]
     #[doc = " configuration file options. Setting this callback allows a LibTidy"]
     #[doc = " application developer to examine command-line and configuration file options"]
@@ -1879,7 +1879,7 @@ extern "C" {
 #[doc = " @param option The option that will be changed."]
 pub type TidyConfigChangeCallback =
     ::std::option::Option<unsafe extern "C" fn(tdoc: TidyDoc, option: TidyOption)>;
-extern "C" {
+extern "C-unwind" {
     #[doc = " Applications using TidyLib may want to be informed when changes to options"]
     #[doc = " are made. Temporary changes made internally by Tidy are not reported, but"]
     #[doc = " permanent changes made by Tidy (such as indent-spaces or output-encoding)"]
@@ -1888,20 +1888,20 @@ extern "C" {
     #[doc = " @result Returns `yes` upon success."]
     pub fn tidySetConfigChangeCallback(tdoc: TidyDoc, pCallback: TidyConfigChangeCallback) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get ID of given Option"]
     #[doc = " @param opt An instance of a TidyOption to query."]
     #[doc = " @result The TidyOptionId of the given option."]
     pub fn tidyOptGetId(opt: TidyOption) -> TidyOptionId;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Returns the TidyOptionId (enum value) by providing the name of a Tidy"]
     #[doc = " configuration option."]
     #[doc = " @param optnam The name of the option ID to retrieve."]
     #[doc = " @result The TidyOptionId of the given `optname`."]
     pub fn tidyOptGetIdForName(optnam: ctmbstr) -> TidyOptionId;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Initiates an iterator for a list of TidyOption instances, which allows you"]
     #[doc = " to iterate through all of the available options. In order to iterate through"]
     #[doc = " the available options, initiate the iterator with this function, and then"]
@@ -1919,7 +1919,7 @@ extern "C" {
     #[doc = "         current position in a list within LibTidy."]
     pub fn tidyGetOptionList(tdoc: TidyDoc) -> TidyIterator;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Given a valid TidyIterator initiated with tidyGetOptionList(), returns"]
     #[doc = " the instance of the next TidyOption."]
     #[doc = " @note This function will return internal-only option types including"]
@@ -1929,36 +1929,36 @@ extern "C" {
     #[doc = " @result An instance of TidyOption."]
     pub fn tidyGetNextOption(tdoc: TidyDoc, pos: *mut TidyIterator) -> TidyOption;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Retrieves an instance of TidyOption given a valid TidyOptionId."]
     #[doc = " @result An instance of TidyOption matching the provided TidyOptionId."]
     pub fn tidyGetOption(tdoc: TidyDoc, optId: TidyOptionId) -> TidyOption;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Returns an instance of TidyOption by providing the name of a Tidy"]
     #[doc = " configuration option."]
     #[doc = " @result The TidyOption of the given `optname`."]
     pub fn tidyGetOptionByName(tdoc: TidyDoc, optnam: ctmbstr) -> TidyOption;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get name of given Option"]
     #[doc = " @param opt An instance of a TidyOption to query."]
     #[doc = " @result The name of the given option."]
     pub fn tidyOptGetName(opt: TidyOption) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get datatype of given Option"]
     #[doc = " @param opt An instance of a TidyOption to query."]
     #[doc = " @result The TidyOptionType of the given option."]
     pub fn tidyOptGetType(opt: TidyOption) -> TidyOptionType;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Indicates that an option takes a list of items."]
     #[doc = " @param opt An instance of a TidyOption to query."]
     #[doc = " @result A bool indicating whether or not the option accepts a list."]
     pub fn tidyOptionIsList(opt: TidyOption) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Is Option read-only? Some options (mainly internal use only options) are"]
     #[doc = " read-only."]
     #[doc = " @deprecated This is no longer a valid test for the public API; instead"]
@@ -1969,32 +1969,32 @@ extern "C" {
     #[doc = "         option is read-only."]
     pub fn tidyOptIsReadOnly(opt: TidyOption) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get category of given Option"]
     #[doc = " @param opt An instance of a TidyOption to query."]
     #[doc = " @result The TidyConfigCategory of the specified option."]
     pub fn tidyOptGetCategory(opt: TidyOption) -> TidyConfigCategory;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get default value of given Option as a string"]
     #[doc = " @param opt An instance of a TidyOption to query."]
     #[doc = " @result A string indicating the default value of the specified option."]
     pub fn tidyOptGetDefault(opt: TidyOption) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get default value of given Option as an unsigned integer"]
     #[doc = " @param opt An instance of a TidyOption to query."]
     #[doc = " @result An unsigned integer indicating the default value of the specified"]
     #[doc = "         option."]
     pub fn tidyOptGetDefaultInt(opt: TidyOption) -> ulong;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get default value of given Option as a Boolean value"]
     #[doc = " @param opt An instance of a TidyOption to query."]
     #[doc = " @result A boolean indicating the default value of the specified option."]
     pub fn tidyOptGetDefaultBool(opt: TidyOption) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Initiates an iterator for a list of TidyOption pick-list values, which"]
     #[doc = " allows you iterate through all of the available option values. In order to"]
     #[doc = " iterate through the available values, initiate the iterator with this"]
@@ -2011,61 +2011,61 @@ extern "C" {
     #[doc = "         current position in a list within LibTidy."]
     pub fn tidyOptGetPickList(opt: TidyOption) -> TidyIterator;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Given a valid TidyIterator initiated with tidyOptGetPickList(), returns a"]
     #[doc = " string representing a possible option value."]
     #[doc = " @result A string containing the next pick-list option value."]
     pub fn tidyOptGetNextPick(opt: TidyOption, pos: *mut TidyIterator) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the current value of the option ID for the given document."]
     #[doc = " @remark The optId *must* have a @ref TidyOptionType of @ref TidyString!"]
     pub fn tidyOptGetValue(tdoc: TidyDoc, optId: TidyOptionId) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Set the option value as a string."]
     #[doc = " @remark The optId *must* have a @ref TidyOptionType of @ref TidyString!"]
     #[doc = " @result Returns a bool indicating success or failure."]
     pub fn tidyOptSetValue(tdoc: TidyDoc, optId: TidyOptionId, val: ctmbstr) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Set named option value as a string, regardless of the @ref TidyOptionType."]
     #[doc = " @remark This is good setter if you are unsure of the type."]
     #[doc = " @result Returns a bool indicating success or failure."]
     pub fn tidyOptParseValue(tdoc: TidyDoc, optnam: ctmbstr, val: ctmbstr) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get current option value as an integer."]
     #[doc = " @result Returns the integer value of the specified option."]
     pub fn tidyOptGetInt(tdoc: TidyDoc, optId: TidyOptionId) -> ulong;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Set option value as an integer."]
     #[doc = " @result Returns a bool indicating success or failure."]
     pub fn tidyOptSetInt(tdoc: TidyDoc, optId: TidyOptionId, val: ulong) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get current option value as a Boolean flag."]
     #[doc = " @result Returns a bool indicating the value."]
     pub fn tidyOptGetBool(tdoc: TidyDoc, optId: TidyOptionId) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Set option value as a Boolean flag."]
     #[doc = " @result Returns a bool indicating success or failure."]
     pub fn tidyOptSetBool(tdoc: TidyDoc, optId: TidyOptionId, val: Bool) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Reset option to default value by ID."]
     #[doc = " @result Returns a bool indicating success or failure."]
     pub fn tidyOptResetToDefault(tdoc: TidyDoc, opt: TidyOptionId) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Reset all options to their default values."]
     #[doc = " @param tdoc The tidy document for which to reset all values."]
     #[doc = " @result Returns a bool indicating success or failure."]
     pub fn tidyOptResetAllToDefault(tdoc: TidyDoc) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Take a snapshot of current config settings. These settings are stored"]
     #[doc = " within the tidy document. Note, however, that snapshots do not reliably"]
     #[doc = " survive the tidyParseXXX() process, as Tidy uses the snapshot mechanism"]
@@ -2075,25 +2075,25 @@ extern "C" {
     #[doc = " @result Returns a bool indicating success or failure."]
     pub fn tidyOptSnapshot(tdoc: TidyDoc) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Apply a snapshot of config settings to a document."]
     #[doc = " @param tdoc The tidy document for which to apply a snapshot."]
     #[doc = " @result Returns a bool indicating success or failure."]
     pub fn tidyOptResetToSnapshot(tdoc: TidyDoc) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Any settings different than default?"]
     #[doc = " @param tdoc The tidy document to check."]
     #[doc = " @result Returns a bool indicating whether or not a difference exists."]
     pub fn tidyOptDiffThanDefault(tdoc: TidyDoc) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Any settings different than snapshot?"]
     #[doc = " @param tdoc The tidy document to check."]
     #[doc = " @result Returns a bool indicating whether or not a difference exists."]
     pub fn tidyOptDiffThanSnapshot(tdoc: TidyDoc) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Copy current configuration settings from one document to another. Note"]
     #[doc = " that the destination document's existing settings will be stored as that"]
     #[doc = " document's snapshot prior to having its option values overwritten by the"]
@@ -2101,20 +2101,20 @@ extern "C" {
     #[doc = " @result Returns a bool indicating success or failure."]
     pub fn tidyOptCopyConfig(tdocTo: TidyDoc, tdocFrom: TidyDoc) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get character encoding name. Used with @ref TidyCharEncoding,"]
     #[doc = " @ref TidyOutCharEncoding, and @ref TidyInCharEncoding."]
     #[doc = " @result The encoding name as a string for the specified option."]
     pub fn tidyOptGetEncName(tdoc: TidyDoc, optId: TidyOptionId) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the current pick list value for the option ID, which can be useful for"]
     #[doc = " enum types."]
     #[doc = " @result Returns a string indicating the current value of the specified"]
     #[doc = "         option."]
     pub fn tidyOptGetCurrPick(tdoc: TidyDoc, optId: TidyOptionId) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Initiates an iterator for a list of user-declared tags, including autonomous"]
     #[doc = " custom tags detected in the document if @ref TidyUseCustomTags is not set to"]
     #[doc = " **no**. This iterator allows you to iterate through all of the custom tags."]
@@ -2132,7 +2132,7 @@ extern "C" {
     #[doc = "         current position in a list within LibTidy."]
     pub fn tidyOptGetDeclTagList(tdoc: TidyDoc) -> TidyIterator;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Given a valid TidyIterator initiated with tidyOptGetDeclTagList(), returns a"]
     #[doc = " string representing a user-declared or autonomous custom tag."]
     #[doc = " @remark Specifying optId limits the scope of the tags to one of"]
@@ -2147,7 +2147,7 @@ extern "C" {
         iter: *mut TidyIterator,
     ) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Initiates an iterator for a list of priority attributes. This iterator"]
     #[doc = " allows you to iterate through all of the priority attributes defined with"]
     #[doc = " the `priority-attributes` configuration option. In order to iterate through"]
@@ -2165,13 +2165,13 @@ extern "C" {
     #[doc = "         current position in a list within LibTidy."]
     pub fn tidyOptGetPriorityAttrList(tdoc: TidyDoc) -> TidyIterator;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Given a valid TidyIterator initiated with tidyOptGetPriorityAttrList(),"]
     #[doc = " returns a string representing a priority attribute."]
     #[doc = " @result A string containing the next tag."]
     pub fn tidyOptGetNextPriorityAttr(tdoc: TidyDoc, iter: *mut TidyIterator) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Initiates an iterator for a list of muted messages. This iterator allows"]
     #[doc = " you to iterate through all of the priority attributes defined with the"]
     #[doc = " `mute` configuration option. In order to iterate through the list, initiate"]
@@ -2189,18 +2189,18 @@ extern "C" {
     #[doc = "         current position in a list within LibTidy."]
     pub fn tidyOptGetMutedMessageList(tdoc: TidyDoc) -> TidyIterator;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Given a valid TidyIterator initiated with tidyOptGetMutedMessageList(),"]
     #[doc = " returns a string representing a muted message."]
     #[doc = " @result A string containing the next tag."]
     pub fn tidyOptGetNextMutedMessage(tdoc: TidyDoc, iter: *mut TidyIterator) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the description of the specified option."]
     #[doc = " @result Returns a string containing a description of the given option."]
     pub fn tidyOptGetDoc(tdoc: TidyDoc, opt: TidyOption) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Initiates an iterator for a list of options related to a given option. This"]
     #[doc = " iterator allows you to iterate through all of the related options, if any."]
     #[doc = " In order to iterate through the options, initiate the iterator with this"]
@@ -2217,7 +2217,7 @@ extern "C" {
     #[doc = "         current position in a list within LibTidy."]
     pub fn tidyOptGetDocLinksList(tdoc: TidyDoc, opt: TidyOption) -> TidyIterator;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Given a valid TidyIterator initiated with tidyOptGetDocLinksList(), returns"]
     #[doc = " a TidyOption instance."]
     #[doc = " @result Returns in instance of TidyOption."]
@@ -2251,7 +2251,7 @@ pub struct _TidyInputSource {
 }
 #[doc = " This type defines an input source capable of delivering raw bytes of input."]
 pub type TidyInputSource = _TidyInputSource;
-extern "C" {
+extern "C-unwind" {
     #[doc = " Facilitates user defined source by providing an entry point to marshal"]
     #[doc = " pointers-to-functions. This is needed by .NET, and possibly other language"]
     #[doc = " bindings."]
@@ -2264,17 +2264,17 @@ extern "C" {
         endFunc: TidyEOFFunc,
     ) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Helper: get next byte from input source."]
     #[doc = " @param source A pointer to your input source."]
     #[doc = " @result Returns a byte as an unsigned integer."]
     pub fn tidyGetByte(source: *mut TidyInputSource) -> uint;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Helper: unget byte back to input source."]
     pub fn tidyUngetByte(source: *mut TidyInputSource, byteValue: uint);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Helper: check if input source at end."]
     #[doc = " @param source The input source."]
     #[doc = " @result Returns a bool indicating whether or not the source is at EOF."]
@@ -2296,7 +2296,7 @@ pub struct _TidyOutputSink {
 #[doc = " This type defines an output destination capable of accepting raw bytes"]
 #[doc = " of output"]
 pub type TidyOutputSink = _TidyOutputSink;
-extern "C" {
+extern "C-unwind" {
     #[doc = " Facilitates user defined sinks by providing an entry point to marshal"]
     #[doc = " pointers-to-functions. This is needed by .NET, and possibly other language"]
     #[doc = " bindings."]
@@ -2307,17 +2307,17 @@ extern "C" {
         pbFunc: TidyPutByteFunc,
     ) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Helper: send a byte to output."]
     pub fn tidyPutByte(sink: *mut TidyOutputSink, byteValue: uint);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Set the file path to use for reports when `TidyEmacs` is being used. This"]
     #[doc = " function provides a proper interface for using the hidden, internal-only"]
     #[doc = " `TidyEmacsFile` configuration option."]
     pub fn tidySetEmacsFile(tdoc: TidyDoc, filePath: ctmbstr);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the file path to use for reports when `TidyEmacs` is being used. This"]
     #[doc = " function provides a proper interface for using the hidden, internal-only"]
     #[doc = " `TidyEmacsFile` configuration option."]
@@ -2325,17 +2325,17 @@ extern "C" {
     #[doc = " @result Returns a string indicating the file path."]
     pub fn tidyGetEmacsFile(tdoc: TidyDoc) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Set error sink to named file."]
     #[doc = " @result Returns a file handle."]
     pub fn tidySetErrorFile(tdoc: TidyDoc, errfilnam: ctmbstr) -> *mut FILE;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Set error sink to given buffer."]
     #[doc = " @result Returns 0 upon success or a standard error number."]
     pub fn tidySetErrorBuffer(tdoc: TidyDoc, errbuf: *mut TidyBuffer) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Set error sink to given generic sink."]
     #[doc = " @result Returns 0 upon success or a standard error number."]
     pub fn tidySetErrorSink(tdoc: TidyDoc, sink: *mut TidyOutputSink) -> ::std::os::raw::c_int;
@@ -2359,7 +2359,7 @@ pub type TidyReportFilter = ::std::option::Option<
         mssg: ctmbstr,
     ) -> Bool,
 >;
-extern "C" {
+extern "C-unwind" {
     #[doc = " This function informs Tidy to use the specified callback to send reports."]
     pub fn tidySetReportFilter(tdoc: TidyDoc, filtCallback: TidyReportFilter) -> Bool;
 }
@@ -2390,7 +2390,7 @@ pub type TidyReportCallback = ::std::option::Option<
         args: va_list,
     ) -> Bool,
 >;
-extern "C" {
+extern "C-unwind" {
     #[doc = " This function informs Tidy to use the specified callback to send reports."]
     pub fn tidySetReportCallback(tdoc: TidyDoc, filtCallback: TidyReportCallback) -> Bool;
 }
@@ -2403,17 +2403,17 @@ extern "C" {
 #[doc = "         report in its own output sink, or `no` if Tidy should suppress it."]
 pub type TidyMessageCallback =
     ::std::option::Option<unsafe extern "C" fn(tmessage: TidyMessage) -> Bool>;
-extern "C" {
+extern "C-unwind" {
     #[doc = " This function informs Tidy to use the specified callback to send reports."]
     pub fn tidySetMessageCallback(tdoc: TidyDoc, filtCallback: TidyMessageCallback) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the tidy document this message comes from."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns the TidyDoc that generated the message."]
     pub fn tidyGetMessageDoc(tmessage: TidyMessage) -> TidyDoc;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the message code."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns a code representing the message. This code can be used"]
@@ -2423,7 +2423,7 @@ extern "C" {
     #[doc = "         enum field or use the message key string value."]
     pub fn tidyGetMessageCode(tmessage: TidyMessage) -> uint;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the message key string."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns a string representing the message. This string is intended"]
@@ -2431,26 +2431,26 @@ extern "C" {
     #[doc = "         in your own applications."]
     pub fn tidyGetMessageKey(tmessage: TidyMessage) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the line number the message applies to."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns the line number, if any, that generated the message."]
     pub fn tidyGetMessageLine(tmessage: TidyMessage) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the column the message applies to."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns the column number, if any, that generated the message."]
     pub fn tidyGetMessageColumn(tmessage: TidyMessage) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the TidyReportLevel of the message."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns a TidyReportLevel indicating the severity or status of the"]
     #[doc = "         message."]
     pub fn tidyGetMessageLevel(tmessage: TidyMessage) -> TidyReportLevel;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the muted status of the message, that is, whether or not the"]
     #[doc = " current configuration indicated that this message should be muted."]
     #[doc = " @param tmessage Specify the message that you are querying."]
@@ -2458,77 +2458,77 @@ extern "C" {
     #[doc = "         message."]
     pub fn tidyGetMessageIsMuted(tmessage: TidyMessage) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the default format string, which is the format string for the message"]
     #[doc = " in Tidy's default localization (en_us)."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns the default localization format string of the message."]
     pub fn tidyGetMessageFormatDefault(tmessage: TidyMessage) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the localized format string. If a localized version of the format string"]
     #[doc = " doesn't exist, then the default version will be returned."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns the localized format string of the message."]
     pub fn tidyGetMessageFormat(tmessage: TidyMessage) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the message with the format string already completed, in Tidy's"]
     #[doc = " default localization."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns the message in the default localization."]
     pub fn tidyGetMessageDefault(tmessage: TidyMessage) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the message with the format string already completed, in Tidy's"]
     #[doc = " current localization."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns the message in the current localization."]
     pub fn tidyGetMessage(tmessage: TidyMessage) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the position part part of the message in the default language."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns the positional part of a string as Tidy would display it"]
     #[doc = "         in the console application."]
     pub fn tidyGetMessagePosDefault(tmessage: TidyMessage) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the position part part of the message in the current language."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns the positional part of a string as Tidy would display it"]
     #[doc = "         in the console application."]
     pub fn tidyGetMessagePos(tmessage: TidyMessage) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the prefix part of a message in the default language."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns the message prefix part of a string as Tidy would display"]
     #[doc = "         it in the console application."]
     pub fn tidyGetMessagePrefixDefault(tmessage: TidyMessage) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the prefix part of a message in the current language."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns the message prefix part of a string as Tidy would display"]
     #[doc = "         it in the console application."]
     pub fn tidyGetMessagePrefix(tmessage: TidyMessage) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the complete message as Tidy would emit it in the default localization."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns the complete message just as Tidy would display it on the"]
     #[doc = "         console."]
     pub fn tidyGetMessageOutputDefault(tmessage: TidyMessage) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the complete message as Tidy would emit it in the current localization."]
     #[doc = " @param tmessage Specify the message that you are querying."]
     #[doc = " @result Returns the complete message just as Tidy would display it on the"]
     #[doc = "         console."]
     pub fn tidyGetMessageOutput(tmessage: TidyMessage) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Initiates an iterator for a list of arguments related to a given message."]
     #[doc = " This iterator allows you to iterate through all of the arguments, if any."]
     #[doc = " In order to iterate through the arguments, initiate the iterator with this"]
@@ -2546,7 +2546,7 @@ extern "C" {
     #[doc = "         current position in a list within LibTidy."]
     pub fn tidyGetMessageArguments(tmessage: TidyMessage) -> TidyIterator;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Given a valid TidyIterator initiated with tidyGetMessageArguments(), returns"]
     #[doc = " an instance of the opaque type TidyMessageArgument, which serves as a token"]
     #[doc = " against which the remaining argument API functions may be used to query"]
@@ -2557,7 +2557,7 @@ extern "C" {
         iter: *mut TidyIterator,
     ) -> TidyMessageArgument;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Returns the `TidyFormatParameterType` of the given message argument."]
     #[doc = " @result Returns the type of parameter of type TidyFormatParameterType."]
     pub fn tidyGetArgType(
@@ -2565,26 +2565,26 @@ extern "C" {
         arg: *mut TidyMessageArgument,
     ) -> TidyFormatParameterType;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Returns the format specifier of the given message argument. The memory for"]
     #[doc = " this string is cleared upon termination of the callback, so do be sure to"]
     #[doc = " make your own copy."]
     #[doc = " @result Returns the format specifier string of the given argument."]
     pub fn tidyGetArgFormat(tmessage: TidyMessage, arg: *mut TidyMessageArgument) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Returns the string value of the given message argument. An assertion"]
     #[doc = " will be generated if the argument type is not a string."]
     #[doc = " @result Returns the string value of the given argument."]
     pub fn tidyGetArgValueString(tmessage: TidyMessage, arg: *mut TidyMessageArgument) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Returns the unsigned integer value of the given message argument. An"]
     #[doc = " assertion will be generated if the argument type is not an unsigned int."]
     #[doc = " @result Returns the unsigned integer value of the given argument."]
     pub fn tidyGetArgValueUInt(tmessage: TidyMessage, arg: *mut TidyMessageArgument) -> uint;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Returns the integer value of the given message argument. An assertion"]
     #[doc = " will be generated if the argument type is not an integer."]
     #[doc = " @result Returns the integer value of the given argument."]
@@ -2593,7 +2593,7 @@ extern "C" {
         arg: *mut TidyMessageArgument,
     ) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = "  Returns the double value of the given message argument. An assertion"]
     #[doc = "  will be generated if the argument type is not a double."]
     #[doc = " @result Returns the double value of the given argument."]
@@ -2609,19 +2609,19 @@ extern "C" {
 pub type TidyPPProgress = ::std::option::Option<
     unsafe extern "C" fn(tdoc: TidyDoc, line: uint, col: uint, destLine: uint),
 >;
-extern "C" {
+extern "C-unwind" {
     #[doc = " This function informs Tidy to use the specified callback for tracking the"]
     #[doc = " pretty-printing process progress."]
     pub fn tidySetPrettyPrinterCallback(tdoc: TidyDoc, callback: TidyPPProgress) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Parse markup in named file."]
     #[doc = " @result Returns the highest of `2` indicating that errors were present in"]
     #[doc = "         the document, `1` indicating warnings, and `0` in the case of"]
     #[doc = "         everything being okay."]
     pub fn tidyParseFile(tdoc: TidyDoc, filename: ctmbstr) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Parse markup from the standard input."]
     #[doc = " @param tdoc The tidy document to use for parsing."]
     #[doc = " @result Returns the highest of `2` indicating that errors were present in"]
@@ -2629,7 +2629,7 @@ extern "C" {
     #[doc = "         everything being okay."]
     pub fn tidyParseStdin(tdoc: TidyDoc) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Parse markup in given string. Note that the supplied string is of type"]
     #[doc = " `ctmbstr` based on `char` and therefore doesn't support the use of"]
     #[doc = " UTF-16 strings. Use `tidyParseBuffer()` if parsing multibyte strings."]
@@ -2638,56 +2638,56 @@ extern "C" {
     #[doc = "         everything being okay."]
     pub fn tidyParseString(tdoc: TidyDoc, content: ctmbstr) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Parse markup in given buffer."]
     #[doc = " @result Returns the highest of `2` indicating that errors were present in"]
     #[doc = "         the document, `1` indicating warnings, and `0` in the case of"]
     #[doc = "         everything being okay."]
     pub fn tidyParseBuffer(tdoc: TidyDoc, buf: *mut TidyBuffer) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Parse markup in given generic input source."]
     #[doc = " @result Returns the highest of `2` indicating that errors were present in"]
     #[doc = "         the document, `1` indicating warnings, and `0` in the case of"]
     #[doc = "         everything being okay."]
     pub fn tidyParseSource(tdoc: TidyDoc, source: *mut TidyInputSource) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Execute configured cleanup and repair operations on parsed markup."]
     #[doc = " @param tdoc The tidy document to use."]
     #[doc = " @result An integer representing the status."]
     pub fn tidyCleanAndRepair(tdoc: TidyDoc) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Reports the document type and diagnostic statistics on parsed and repaired"]
     #[doc = " markup. You must call tidyCleanAndRepair() before using this function."]
     #[doc = " @param tdoc The tidy document to use."]
     #[doc = " @result An integer representing the status."]
     pub fn tidyRunDiagnostics(tdoc: TidyDoc) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Reports the document type into the output sink."]
     #[doc = " @param tdoc The tidy document to use."]
     #[doc = " @result An integer representing the status."]
     pub fn tidyReportDoctype(tdoc: TidyDoc) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Save the tidy document to the named file."]
     #[doc = " @result An integer representing the status."]
     pub fn tidySaveFile(tdoc: TidyDoc, filename: ctmbstr) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Save the tidy document to standard output (FILE*)."]
     #[doc = " @param tdoc The tidy document to save."]
     #[doc = " @result An integer representing the status."]
     pub fn tidySaveStdout(tdoc: TidyDoc) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Save the tidy document to given TidyBuffer object."]
     #[doc = " @result An integer representing the status."]
     pub fn tidySaveBuffer(tdoc: TidyDoc, buf: *mut TidyBuffer) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Save the tidy document to an application buffer. If TidyShowMarkup and the"]
     #[doc = " document has no errors, or TidyForceOutput, then the current document (per"]
     #[doc = " the current configuration) will be pretty printed to this application"]
@@ -2701,192 +2701,192 @@ extern "C" {
         buflen: *mut uint,
     ) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Save to given generic output sink."]
     #[doc = " @result An integer representing the status."]
     pub fn tidySaveSink(tdoc: TidyDoc, sink: *mut TidyOutputSink) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Save current settings to named file. Only writes non-default values."]
     #[doc = " @result An integer representing the status."]
     pub fn tidyOptSaveFile(tdoc: TidyDoc, cfgfil: ctmbstr) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Save current settings to given output sink. Only non-default values are"]
     #[doc = " written."]
     #[doc = " @result An integer representing the status."]
     pub fn tidyOptSaveSink(tdoc: TidyDoc, sink: *mut TidyOutputSink) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the root node."]
     #[doc = " @param tdoc The document to query."]
     #[doc = " @result Returns a tidy node."]
     pub fn tidyGetRoot(tdoc: TidyDoc) -> TidyNode;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the HTML node."]
     #[doc = " @param tdoc The document to query."]
     #[doc = " @result Returns a tidy node."]
     pub fn tidyGetHtml(tdoc: TidyDoc) -> TidyNode;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the HEAD node."]
     #[doc = " @param tdoc The document to query."]
     #[doc = " @result Returns a tidy node."]
     pub fn tidyGetHead(tdoc: TidyDoc) -> TidyNode;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the BODY node."]
     #[doc = " @param tdoc The document to query."]
     #[doc = " @result Returns a tidy node."]
     pub fn tidyGetBody(tdoc: TidyDoc) -> TidyNode;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the parent of the indicated node."]
     #[doc = " @param tnod The node to query."]
     #[doc = " @result Returns a tidy node."]
     pub fn tidyGetParent(tnod: TidyNode) -> TidyNode;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the child of the indicated node."]
     #[doc = " @param tnod The node to query."]
     #[doc = " @result Returns a tidy node."]
     pub fn tidyGetChild(tnod: TidyNode) -> TidyNode;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the next sibling node."]
     #[doc = " @param tnod The node to query."]
     #[doc = " @result Returns a tidy node."]
     pub fn tidyGetNext(tnod: TidyNode) -> TidyNode;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the previous sibling node."]
     #[doc = " @param tnod The node to query."]
     #[doc = " @result Returns a tidy node."]
     pub fn tidyGetPrev(tnod: TidyNode) -> TidyNode;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Remove the indicated node."]
     #[doc = " @result Returns the next tidy node."]
     pub fn tidyDiscardElement(tdoc: TidyDoc, tnod: TidyNode) -> TidyNode;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the first attribute."]
     #[doc = " @param tnod The node for which to get attributes."]
     #[doc = " @result Returns an instance of TidyAttr."]
     pub fn tidyAttrFirst(tnod: TidyNode) -> TidyAttr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the next attribute."]
     #[doc = " @param tattr The current attribute, so the next one can be returned."]
     #[doc = " @result Returns and instance of TidyAttr."]
     pub fn tidyAttrNext(tattr: TidyAttr) -> TidyAttr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the name of a TidyAttr instance."]
     #[doc = " @param tattr The tidy attribute to query."]
     #[doc = " @result Returns a string indicating the name of the attribute."]
     pub fn tidyAttrName(tattr: TidyAttr) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the value of a TidyAttr instance."]
     #[doc = " @param tattr The tidy attribute to query."]
     #[doc = " @result Returns a string indicating the value of the attribute."]
     pub fn tidyAttrValue(tattr: TidyAttr) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Discard an attribute."]
     pub fn tidyAttrDiscard(itdoc: TidyDoc, tnod: TidyNode, tattr: TidyAttr);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the attribute ID given a tidy attribute."]
     #[doc = " @param tattr The attribute to query."]
     #[doc = " @result Returns the TidyAttrId of the given attribute."]
     pub fn tidyAttrGetId(tattr: TidyAttr) -> TidyAttrId;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Indicates whether or not a given attribute is an event attribute."]
     #[doc = " @param tattr The attribute to query."]
     #[doc = " @result Returns a bool indicating whether or not the attribute is an event."]
     pub fn tidyAttrIsEvent(tattr: TidyAttr) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get an instance of TidyAttr by specifying an attribute ID."]
     #[doc = " @result Returns a TidyAttr instance."]
     pub fn tidyAttrGetById(tnod: TidyNode, attId: TidyAttrId) -> TidyAttr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the type of node."]
     #[doc = " @param tnod The node to query."]
     #[doc = " @result Returns the type of node as TidyNodeType."]
     pub fn tidyNodeGetType(tnod: TidyNode) -> TidyNodeType;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the name of the node."]
     #[doc = " @param tnod The node to query."]
     #[doc = " @result Returns a string indicating the name of the node."]
     pub fn tidyNodeGetName(tnod: TidyNode) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Indicates whether or not a node is a text node."]
     #[doc = " @param tnod The node to query."]
     #[doc = " @result Returns a bool indicating whether or not the node is a text node."]
     pub fn tidyNodeIsText(tnod: TidyNode) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Indicates whether or not the node is a propriety type."]
     #[doc = " @result Returns a bool indicating whether or not the node is a proprietary type."]
     pub fn tidyNodeIsProp(tdoc: TidyDoc, tnod: TidyNode) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Indicates whether or not a node represents and HTML header element, such"]
     #[doc = " as h1, h2, etc."]
     #[doc = " @param tnod The node to query."]
     #[doc = " @result Returns a bool indicating whether or not the node is an HTML header."]
     pub fn tidyNodeIsHeader(tnod: TidyNode) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Indicates whether or not the node has text."]
     #[doc = " @result Returns the type of node as TidyNodeType."]
     pub fn tidyNodeHasText(tdoc: TidyDoc, tnod: TidyNode) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Gets the text of a node and places it into the given TidyBuffer. The text will be terminated with a `TidyNewline`."]
     #[doc = " If you want the raw utf-8 stream see `tidyNodeGetValue()`."]
     #[doc = " @result Returns a bool indicating success or not."]
     pub fn tidyNodeGetText(tdoc: TidyDoc, tnod: TidyNode, buf: *mut TidyBuffer) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the value of the node. This copies the unescaped value of this node into"]
     #[doc = " the given TidyBuffer at UTF-8."]
     #[doc = " @result Returns a bool indicating success or not."]
     pub fn tidyNodeGetValue(tdoc: TidyDoc, tnod: TidyNode, buf: *mut TidyBuffer) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the tag ID of the node."]
     #[doc = " @param tnod The node to query."]
     #[doc = " @result Returns the tag ID of the node as TidyTagId."]
     pub fn tidyNodeGetId(tnod: TidyNode) -> TidyTagId;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the line number where the node occurs."]
     #[doc = " @param tnod The node to query."]
     #[doc = " @result Returns the line number."]
     pub fn tidyNodeLine(tnod: TidyNode) -> uint;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get the column location of the node."]
     #[doc = " @param tnod The node to query."]
     #[doc = " @result Returns the column location of the node."]
     pub fn tidyNodeColumn(tnod: TidyNode) -> uint;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Given a message code, return the text key that represents it."]
     #[doc = " @param code The error code to lookup."]
     #[doc = " @result The string representing the error code."]
     pub fn tidyErrorCodeAsKey(code: uint) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Given a text key representing a message code, return the uint that"]
     #[doc = " represents it."]
     #[doc = ""]
@@ -2900,7 +2900,7 @@ extern "C" {
     #[doc = "         not have a matching message code, then UINT_MAX will be returned."]
     pub fn tidyErrorCodeFromKey(code: ctmbstr) -> uint;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Tells Tidy to use a different language for output."]
     #[doc = " @param  languageCode A Windows or POSIX language code, and must match"]
     #[doc = "         a TIDY_LANGUAGE for an installed language."]
@@ -2912,12 +2912,12 @@ extern "C" {
     #[doc = "         not present, Tidy will not try to select from the es_XX variants."]
     pub fn tidySetLanguage(languageCode: ctmbstr) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Gets the current language used by Tidy."]
     #[doc = " @result Returns a string indicating the currently set language."]
     pub fn tidyGetLanguage() -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Provides a string given `messageType` in the current localization for"]
     #[doc = " `quantity`. Some strings have one or more plural forms, and this function"]
     #[doc = " will ensure that the correct singular or plural form is returned for the"]
@@ -2925,20 +2925,35 @@ extern "C" {
     #[doc = " @result Returns the desired string."]
     pub fn tidyLocalizedStringN(messageType: uint, quantity: uint) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Provides a string given `messageType` in the current localization for the"]
     #[doc = " single case."]
     #[doc = " @param messageType The message type."]
     #[doc = " @result Returns the desired string."]
     pub fn tidyLocalizedString(messageType: uint) -> ctmbstr;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Provides a string given `messageType` in the default localization (which"]
     #[doc = " is `en`)."]
     #[doc = " @param messageType The message type."]
     #[doc = " @result Returns the desired string."]
     pub fn tidyDefaultString(messageType: uint) -> ctmbstr;
 }
+extern "C-unwind" {
+    #[doc = " Initiates an iterator for a list of Tidy-installed languages. This"]
+    #[doc = "  iterator allows you to retrieve a list of languages installed in Tidy."]
+    #[doc = " @result Returns a TidyIterator, which is a token used to represent the"]
+    #[doc = "         current position in a list within LibTidy."]
+    pub fn getInstalledLanguageList() -> TidyIterator;
+}
+extern "C-unwind" {
+    #[doc = " Given a valid TidyIterator initiated with getInstalledLanguageList(),"]
+    #[doc = "  returns a string of a Tidy-installed language."]
+    #[doc = " @param iter The iterator token provided initially by"]
+    #[doc = "        getInstalledLanguageList()."]
+    #[doc = " @result Returns a string of a Tidy-installed language."]
+    pub fn getNextInstalledLanguage(iter: *mut TidyIterator) -> ctmbstr;
+}
 #[doc = " @name Forward declarations and typedefs."]
 #[doc = " @{"]
 #[repr(C)]
@@ -2955,20 +2970,20 @@ pub struct _TidyBuffer {
     #[doc = "< Offset of current input position"]
     pub next: uint,
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Initialize data structure using the default allocator"]
     pub fn tidyBufInit(buf: *mut TidyBuffer);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Initialize data structure using the given custom allocator"]
     pub fn tidyBufInitWithAllocator(buf: *mut TidyBuffer, allocator: *mut TidyAllocator);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Free current buffer, allocate given amount, reset input pointer,"]
     #[doc = "use the default allocator"]
     pub fn tidyBufAlloc(buf: *mut TidyBuffer, allocSize: uint);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Free current buffer, allocate given amount, reset input pointer,"]
     #[doc = "use the given custom allocator"]
     pub fn tidyBufAllocWithAllocator(
@@ -2977,56 +2992,56 @@ extern "C" {
         allocSize: uint,
     );
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Expand buffer to given size."]
     #[doc = "  Chunk size is minimum growth. Pass 0 for default of 256 bytes."]
     pub fn tidyBufCheckAlloc(buf: *mut TidyBuffer, allocSize: uint, chunkSize: uint);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Free current contents and zero out"]
     pub fn tidyBufFree(buf: *mut TidyBuffer);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Set buffer bytes to 0"]
     pub fn tidyBufClear(buf: *mut TidyBuffer);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Attach to existing buffer"]
     pub fn tidyBufAttach(buf: *mut TidyBuffer, bp: *mut byte, size: uint);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Detach from buffer.  Caller must free."]
     pub fn tidyBufDetach(buf: *mut TidyBuffer);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Append bytes to buffer.  Expand if necessary."]
     pub fn tidyBufAppend(buf: *mut TidyBuffer, vp: *mut ::std::os::raw::c_void, size: uint);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Append one byte to buffer.  Expand if necessary."]
     pub fn tidyBufPutByte(buf: *mut TidyBuffer, bv: byte);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get byte from end of buffer"]
     pub fn tidyBufPopByte(buf: *mut TidyBuffer) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Get byte from front of buffer.  Increment input offset."]
     pub fn tidyBufGetByte(buf: *mut TidyBuffer) -> ::std::os::raw::c_int;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " At end of buffer?"]
     pub fn tidyBufEndOfInput(buf: *mut TidyBuffer) -> Bool;
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Put a byte back into the buffer.  Decrement input offset."]
     pub fn tidyBufUngetByte(buf: *mut TidyBuffer, bv: byte);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Initialize a buffer input source"]
     pub fn tidyInitInputBuffer(inp: *mut TidyInputSource, buf: *mut TidyBuffer);
 }
-extern "C" {
+extern "C-unwind" {
     #[doc = " Initialize a buffer output sink"]
     pub fn tidyInitOutputBuffer(outp: *mut TidyOutputSink, buf: *mut TidyBuffer);
 }